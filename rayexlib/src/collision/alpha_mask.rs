@@ -0,0 +1,281 @@
+use super::{Polygon2D, Rect2D};
+use raylib::prelude::{Rectangle, Vector2};
+
+/// Opaque pixels of an image region, used to build colliders from sprites
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlphaMask {
+    width: usize,
+    height: usize,
+    opaque: Vec<bool>,
+}
+
+impl AlphaMask {
+    /// Mask from row major opaque flags
+    pub fn new(width: usize, height: usize, opaque: Vec<bool>) -> AlphaMask {
+        assert_eq!(opaque.len(), width * height);
+        AlphaMask {
+            width,
+            height,
+            opaque,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> Vector2 {
+        Vector2::new(self.width as f32, self.height as f32)
+    }
+
+    /// Pixel is opaque, anything outside the mask is not
+    pub fn is_opaque(&self, x: i32, y: i32) -> bool {
+        x >= 0
+            && y >= 0
+            && (x as usize) < self.width
+            && (y as usize) < self.height
+            && self.opaque[y as usize * self.width + x as usize]
+    }
+
+    /// Tight box around every opaque pixel
+    pub fn bounds(&self) -> Option<Rectangle> {
+        let (mut min_x, mut min_y) = (usize::MAX, usize::MAX);
+        let (mut max_x, mut max_y) = (0, 0);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.opaque[y * self.width + x] {
+                    min_x = min_x.min(x);
+                    min_y = min_y.min(y);
+                    max_x = max_x.max(x);
+                    max_y = max_y.max(y);
+                }
+            }
+        }
+
+        (min_x <= max_x).then(|| {
+            Rectangle::new(
+                min_x as f32,
+                min_y as f32,
+                (max_x - min_x + 1) as f32,
+                (max_y - min_y + 1) as f32,
+            )
+        })
+    }
+
+    /// Outer outline of the largest opaque blob along pixel edges, clockwise on screen
+    /// (holes are ignored, blobs only connect through pixel sides)
+    pub fn outline(&self) -> Vec<Vector2> {
+        let Some((label, start)) = self.largest_blob() else {
+            return Vec::new();
+        };
+        let inside =
+            |x: i32, y: i32| self.is_opaque(x, y) && label[y as usize * self.width + x as usize];
+
+        // follow pixel edges with the blob on the right,
+        // starting at the first pixel's top left corner (always a turn)
+        let mut points = vec![Vector2::new(start.0 as f32, start.1 as f32)];
+        let (mut corner, mut direction) = (start, (1, 0));
+        loop {
+            let right = (-direction.1, direction.0);
+            let left = (direction.1, -direction.0);
+            let ahead = |side: (i32, i32)| {
+                inside(
+                    corner.0 + (direction.0 + side.0 - 1) / 2,
+                    corner.1 + (direction.1 + side.1 - 1) / 2,
+                )
+            };
+
+            let next = if !ahead(right) {
+                right
+            } else if ahead(left) {
+                left
+            } else {
+                direction
+            };
+            if next != direction {
+                points.push(Vector2::new(corner.0 as f32, corner.1 as f32));
+            }
+
+            direction = next;
+            corner = (corner.0 + direction.0, corner.1 + direction.1);
+            if corner == start {
+                break;
+            }
+        }
+        points
+    }
+
+    /// Outline with points closer than `tolerance` pixels to a straight run removed
+    pub fn simplified_outline(&self, tolerance: f32) -> Vec<Vector2> {
+        let outline = self.outline();
+        if outline.len() < 4 {
+            return outline;
+        }
+
+        // split the loop at the point farthest from the first
+        let farthest = (1..outline.len())
+            .max_by(|&a, &b| {
+                outline[0]
+                    .distance_to(outline[a])
+                    .total_cmp(&outline[0].distance_to(outline[b]))
+            })
+            .unwrap_or(1);
+
+        let mut first = outline[..=farthest].to_vec();
+        let mut second = outline[farthest..].to_vec();
+        second.push(outline[0]);
+        first = douglas_peucker(&first, tolerance);
+        second = douglas_peucker(&second, tolerance);
+
+        // halves share their end points
+        first.pop();
+        second.pop();
+        first.extend(second);
+        first
+    }
+
+    /// Convex hull of the largest blob's outline, clockwise on screen
+    pub fn hull(&self) -> Vec<Vector2> {
+        convex_hull(self.outline())
+    }
+
+    /// Box around the opaque pixels, positioned relative to the mask's top left
+    pub fn rect(&self) -> Option<Rect2D> {
+        let bounds = self.bounds()?;
+        Some(Rect2D::new(bounds.width, bounds.height).with_position(bounds.x, bounds.y))
+    }
+
+    /// Simplified outline, concave outlines are decomposed [mask center at (0,0)]
+    pub fn polygon(&self, tolerance: f32) -> Option<Polygon2D> {
+        self.centered_polygon(self.simplified_outline(tolerance))
+    }
+
+    /// Convex hull of the outline [mask center at (0,0)]
+    pub fn hull_polygon(&self) -> Option<Polygon2D> {
+        self.centered_polygon(self.hull())
+    }
+
+    fn centered_polygon(&self, points: Vec<Vector2>) -> Option<Polygon2D> {
+        if points.len() < 3 {
+            return None;
+        }
+
+        let center = self.size() / 2.0;
+        Some(Polygon2D::from(
+            points.into_iter().map(|point| point - center).collect(),
+        ))
+    }
+
+    /// Pixels of the largest side connected blob, and its first pixel in reading order
+    fn largest_blob(&self) -> Option<(Vec<bool>, (i32, i32))> {
+        let mut visited = vec![false; self.opaque.len()];
+        let mut best: Option<(Vec<usize>, usize)> = None;
+
+        for first in 0..self.opaque.len() {
+            if !self.opaque[first] || visited[first] {
+                continue;
+            }
+
+            // flood fill
+            let mut blob = Vec::new();
+            let mut stack = vec![first];
+            visited[first] = true;
+            while let Some(i) = stack.pop() {
+                blob.push(i);
+                let (x, y) = ((i % self.width) as i32, (i / self.width) as i32);
+                for (nx, ny) in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                    if !self.is_opaque(nx, ny) {
+                        continue;
+                    }
+                    let j = ny as usize * self.width + nx as usize;
+                    if !visited[j] {
+                        visited[j] = true;
+                        stack.push(j);
+                    }
+                }
+            }
+
+            if best
+                .as_ref()
+                .is_none_or(|(pixels, _)| blob.len() > pixels.len())
+            {
+                best = Some((blob, first));
+            }
+        }
+
+        let (pixels, first) = best?;
+        let mut label = vec![false; self.opaque.len()];
+        for i in pixels {
+            label[i] = true;
+        }
+        Some((
+            label,
+            ((first % self.width) as i32, (first / self.width) as i32),
+        ))
+    }
+}
+
+/// Distance from `point` to the segment `start` → `end`
+fn segment_distance(point: Vector2, start: Vector2, end: Vector2) -> f32 {
+    let edge = end - start;
+    let length_sqr = edge.dot(edge);
+    if length_sqr == 0.0 {
+        return point.distance_to(start);
+    }
+    let t = ((point - start).dot(edge) / length_sqr).clamp(0.0, 1.0);
+    point.distance_to(start + edge * t)
+}
+
+/// Ramer-Douglas-Peucker simplification of an open line, keeps both ends
+fn douglas_peucker(points: &[Vector2], tolerance: f32) -> Vec<Vector2> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let (start, end) = (points[0], points[points.len() - 1]);
+    let (index, distance) = (1..points.len() - 1)
+        .map(|i| (i, segment_distance(points[i], start, end)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or((0, 0.0));
+
+    if distance <= tolerance {
+        return vec![start, end];
+    }
+
+    let mut left = douglas_peucker(&points[..=index], tolerance);
+    left.pop();
+    left.extend(douglas_peucker(&points[index..], tolerance));
+    left
+}
+
+/// Monotone chain hull, clockwise on screen
+fn convex_hull(mut points: Vec<Vector2>) -> Vec<Vector2> {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+
+    let cross =
+        |o: Vector2, a: Vector2, b: Vector2| (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x);
+
+    let mut hull: Vec<Vector2> = Vec::with_capacity(points.len() * 2);
+    for pass in [points.clone(), points.into_iter().rev().collect()] {
+        let floor = hull.len();
+        for point in pass {
+            while hull.len() >= floor + 2
+                && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0.0
+            {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // last point starts the other chain
+        hull.pop();
+    }
+    hull
+}
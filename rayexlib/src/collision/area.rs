@@ -0,0 +1,101 @@
+use super::{CollisionLayer, CollisionObject, Shape2D};
+use raylib::prelude::Rectangle;
+
+/// Change in a body's overlap with an area
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AreaEvent<Id> {
+    /// Body started overlapping this frame
+    Enter(Id),
+    /// Body is still overlapping
+    Stay(Id),
+    /// Body stopped overlapping, or was not passed in this frame
+    Exit(Id),
+}
+
+impl<Id: Copy> AreaEvent<Id> {
+    pub const fn id(&self) -> Id {
+        match self {
+            AreaEvent::Enter(id) | AreaEvent::Stay(id) | AreaEvent::Exit(id) => *id,
+        }
+    }
+}
+
+/// Non solid trigger volume, tracks which bodies overlap its shape
+/// Bodies are only detected if they are on a layer in the shape's mask
+pub struct Area2D<S, Id> {
+    pub shape: S,
+    overlapping: Vec<Id>,
+}
+
+impl<S: CollisionObject, Id: Copy + Eq> Area2D<S, Id> {
+    pub const fn new(shape: S) -> Self {
+        Self {
+            shape,
+            overlapping: Vec::new(),
+        }
+    }
+
+    /// Bodies overlapping since the last update
+    pub fn overlapping(&self) -> &[Id] {
+        &self.overlapping
+    }
+
+    pub fn contains(&self, id: Id) -> bool {
+        self.overlapping.contains(&id)
+    }
+
+    /// Test bodies against the area, returns this frame's events
+    pub fn update<'a>(
+        &mut self,
+        bodies: impl IntoIterator<Item = (Id, &'a dyn CollisionObject)>,
+    ) -> Vec<AreaEvent<Id>> {
+        let mut events = Vec::new();
+        let mut overlapping = Vec::new();
+
+        for (id, body) in bodies {
+            if overlapping.contains(&id) || !self.shape.intersects(body) {
+                continue;
+            }
+
+            events.push(if self.contains(id) {
+                AreaEvent::Stay(id)
+            } else {
+                AreaEvent::Enter(id)
+            });
+            overlapping.push(id);
+        }
+
+        // bodies that left
+        for id in &self.overlapping {
+            if !overlapping.contains(id) {
+                events.push(AreaEvent::Exit(*id));
+            }
+        }
+
+        self.overlapping = overlapping;
+        events
+    }
+
+    /// Forget all bodies, returns their exit events
+    pub fn clear(&mut self) -> Vec<AreaEvent<Id>> {
+        self.overlapping.drain(..).map(AreaEvent::Exit).collect()
+    }
+}
+
+impl<S: CollisionObject, Id> CollisionObject for Area2D<S, Id> {
+    fn shape(&self) -> Shape2D<'_> {
+        self.shape.shape()
+    }
+
+    fn bounds(&self) -> Rectangle {
+        self.shape.bounds()
+    }
+
+    fn layer(&self) -> CollisionLayer {
+        self.shape.layer()
+    }
+
+    fn mask(&self) -> CollisionLayer {
+        self.shape.mask()
+    }
+}
@@ -0,0 +1,194 @@
+use super::{narrow_phase, AlphaMask, CollisionLayer, Rect2D};
+use raylib::prelude::{Color, RaylibDraw, Rectangle, Vector2};
+use std::rc::Rc;
+
+/// Pixel perfect collider placed over a sprite, meant as a narrow phase
+/// check after the bounding boxes overlap (rotation is not supported)
+#[derive(Debug, Clone)]
+pub struct Bitmask2D {
+    /// Top left of the drawn sprite
+    pub position: Vector2,
+    /// World size of one mask pixel
+    pub scale: Vector2,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    pub layer: CollisionLayer,
+    pub mask: CollisionLayer,
+    bits: Rc<AlphaMask>,
+}
+
+impl Bitmask2D {
+    pub fn new(bits: Rc<AlphaMask>) -> Bitmask2D {
+        Bitmask2D {
+            position: Vector2::zero(),
+            scale: Vector2::one(),
+            flip_x: false,
+            flip_y: false,
+            layer: CollisionLayer::DEFAULT,
+            mask: CollisionLayer::ALL,
+            bits,
+        }
+    }
+
+    pub const fn with_position(mut self, x: f32, y: f32) -> Self {
+        self.position.x = x;
+        self.position.y = y;
+        self
+    }
+
+    pub const fn with_scale(mut self, x: f32, y: f32) -> Self {
+        self.scale.x = x;
+        self.scale.y = y;
+        self
+    }
+
+    /// Mirror the mask like a flipped sprite
+    pub const fn with_flip(mut self, flip_x: bool, flip_y: bool) -> Self {
+        self.flip_x = flip_x;
+        self.flip_y = flip_y;
+        self
+    }
+
+    pub const fn with_layer(mut self, layer: CollisionLayer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    pub const fn with_mask(mut self, mask: CollisionLayer) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    pub fn bits(&self) -> &AlphaMask {
+        &self.bits
+    }
+
+    /// World space box covered by the mask
+    pub fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x,
+            self.position.y,
+            self.bits.width() as f32 * self.scale.x,
+            self.bits.height() as f32 * self.scale.y,
+        )
+    }
+
+    /// Opaque mask pixel under a world point
+    pub fn contains_point(&self, point: Vector2) -> bool {
+        let (x, y) = self.pixel_at(point);
+        self.bits.is_opaque(x, y)
+    }
+
+    /// Opaque pixels of both masks overlap (checked at the finer mask's resolution)
+    pub fn overlaps(&self, other: &Bitmask2D) -> bool {
+        if !self.mask.intersects(other.layer) {
+            return false;
+        }
+
+        let (fine, coarse) = if self.scale.x * self.scale.y <= other.scale.x * other.scale.y {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        fine.any_pixel(&coarse.bounds(), |center, _| coarse.contains_point(center))
+    }
+
+    /// Any opaque pixel overlaps the rect (touching doesn't count)
+    pub fn overlaps_rect(&self, rect: &Rect2D) -> bool {
+        if !self.mask.intersects(rect.layer) {
+            return false;
+        }
+
+        let area = Rectangle::new(rect.position.x, rect.position.y, rect.size.x, rect.size.y);
+        self.any_pixel(&area, |_, pixel| {
+            pixel.x < area.x + area.width
+                && area.x < pixel.x + pixel.width
+                && pixel.y < area.y + area.height
+                && area.y < pixel.y + pixel.height
+        })
+    }
+
+    /// Mask pixel under a world point, taking flipping into account
+    fn pixel_at(&self, point: Vector2) -> (i32, i32) {
+        let local = point - self.position;
+        let mut x = (local.x / self.scale.x).floor() as i32;
+        let mut y = (local.y / self.scale.y).floor() as i32;
+        if self.flip_x {
+            x = self.bits.width() as i32 - 1 - x;
+        }
+        if self.flip_y {
+            y = self.bits.height() as i32 - 1 - y;
+        }
+        (x, y)
+    }
+
+    /// World box of the pixel in screen column `column` & row `row` (after flipping)
+    fn cell(&self, column: usize, row: usize) -> Rectangle {
+        Rectangle::new(
+            self.position.x + column as f32 * self.scale.x,
+            self.position.y + row as f32 * self.scale.y,
+            self.scale.x,
+            self.scale.y,
+        )
+    }
+
+    /// Center of a cell, for looking up the pixel drawn there
+    fn center(cell: Rectangle) -> Vector2 {
+        Vector2::new(cell.x + cell.width / 2.0, cell.y + cell.height / 2.0)
+    }
+
+    /// Any opaque pixel inside `area` passes `test(center, cell)`
+    fn any_pixel(&self, area: &Rectangle, test: impl Fn(Vector2, Rectangle) -> bool) -> bool {
+        let bounds = self.bounds();
+        if !narrow_phase::overlaps(&bounds, area) {
+            return false;
+        }
+
+        // only columns & rows inside the area's box
+        let first_column = ((area.x - bounds.x) / self.scale.x).floor().max(0.0) as usize;
+        let first_row = ((area.y - bounds.y) / self.scale.y).floor().max(0.0) as usize;
+        let end_column = (((area.x + area.width - bounds.x) / self.scale.x).ceil() as usize)
+            .min(self.bits.width());
+        let end_row = (((area.y + area.height - bounds.y) / self.scale.y).ceil() as usize)
+            .min(self.bits.height());
+
+        for row in first_row..end_row {
+            for column in first_column..end_column {
+                let cell = self.cell(column, row);
+                let center = Self::center(cell);
+                if self.contains_point(center) && test(center, cell) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+impl Bitmask2D {
+    /// Draw opaque pixels, joined into runs per row
+    pub fn draw(&self, color: Color, raylib: &mut impl RaylibDraw) {
+        let width = self.bits.width();
+        for row in 0..self.bits.height() {
+            let opaque = |column: usize| self.contains_point(Self::center(self.cell(column, row)));
+
+            let mut column = 0;
+            while column < width {
+                if !opaque(column) {
+                    column += 1;
+                    continue;
+                }
+
+                let start = column;
+                while column < width && opaque(column) {
+                    column += 1;
+                }
+                let mut run = self.cell(start, row);
+                run.width *= (column - start) as f32;
+                raylib.draw_rectangle_rec(run, color);
+            }
+        }
+
+        raylib.draw_rectangle_lines_ex(self.bounds(), 1, color.fade(0.5));
+    }
+}
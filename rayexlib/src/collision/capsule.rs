@@ -0,0 +1,127 @@
+use super::{CollisionLayer, CollisionObject, Contact2D, Shape2D};
+use raylib::prelude::{Color, RaylibDraw, Rectangle, Vector2};
+
+/// Segment swept by a circle, rounded ends glide over seams between colliders
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capsule2D {
+    /// Center of the capsule
+    pub position: Vector2,
+    pub radius: f32,
+    /// Distance from the center to either end's circle center
+    pub half_length: f32,
+    /// Radians, 0 is upright
+    pub rotation: f32,
+    pub colliding: bool,
+    pub layer: CollisionLayer,
+    pub mask: CollisionLayer,
+}
+
+impl Capsule2D {
+    /// Upright capsule `height` tall including the rounded ends
+    pub fn new(radius: f32, height: f32) -> Capsule2D {
+        Capsule2D {
+            position: Vector2::zero(),
+            radius,
+            half_length: (height / 2.0 - radius).max(0.0),
+            rotation: 0.0,
+            colliding: false,
+            layer: CollisionLayer::DEFAULT,
+            mask: CollisionLayer::ALL,
+        }
+    }
+
+    pub const fn with_position(mut self, x: f32, y: f32) -> Self {
+        self.position.x = x;
+        self.position.y = y;
+        self
+    }
+
+    pub const fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub const fn with_layer(mut self, layer: CollisionLayer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    pub const fn with_mask(mut self, mask: CollisionLayer) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Total height including the rounded ends
+    pub fn height(&self) -> f32 {
+        (self.half_length + self.radius) * 2.0
+    }
+
+    /// Unit direction from the center to the bottom end
+    pub fn axis(&self) -> Vector2 {
+        Vector2::new(-self.rotation.sin(), self.rotation.cos())
+    }
+
+    /// Centers of the rounded ends (top, bottom)
+    pub fn points(&self) -> [Vector2; 2] {
+        let offset = self.axis() * self.half_length;
+        [self.position - offset, self.position + offset]
+    }
+
+    /// Reset colliding
+    pub fn reset_colliding(&mut self) {
+        self.colliding = false;
+    }
+}
+
+impl Capsule2D {
+    /// Push capsule out of `other`
+    pub fn collide(&mut self, other: &dyn CollisionObject) -> Option<Contact2D> {
+        let contact = self.contact(other);
+        if let Some(contact) = &contact {
+            self.position += contact.separation();
+        }
+
+        self.colliding = contact.is_some();
+        contact
+    }
+}
+
+impl Capsule2D {
+    pub fn draw(&self, color: Color, raylib: &mut impl RaylibDraw) {
+        let [top, bottom] = self.points();
+        let side = Vector2::new(-self.axis().y, self.axis().x) * self.radius;
+
+        // outline
+        raylib.draw_circle_lines(top.x as i32, top.y as i32, self.radius, color);
+        raylib.draw_circle_lines(bottom.x as i32, bottom.y as i32, self.radius, color);
+        raylib.draw_line_v(top + side, bottom + side, color);
+        raylib.draw_line_v(top - side, bottom - side, color);
+
+        // center
+        raylib.draw_circle_v(self.position, self.radius / 5.0, color.fade(0.5));
+    }
+}
+
+impl CollisionObject for Capsule2D {
+    fn shape(&self) -> Shape2D<'_> {
+        Shape2D::Capsule(*self)
+    }
+
+    fn layer(&self) -> CollisionLayer {
+        self.layer
+    }
+
+    fn mask(&self) -> CollisionLayer {
+        self.mask
+    }
+
+    fn bounds(&self) -> Rectangle {
+        let [top, bottom] = self.points();
+        Rectangle::new(
+            top.x.min(bottom.x) - self.radius,
+            top.y.min(bottom.y) - self.radius,
+            (top.x - bottom.x).abs() + self.radius * 2.0,
+            (top.y - bottom.y).abs() + self.radius * 2.0,
+        )
+    }
+}
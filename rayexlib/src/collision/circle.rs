@@ -1,9 +1,12 @@
-use raylib::prelude::{Color, RaylibDraw, Vector2};
+use super::{CollisionLayer, CollisionObject, Contact2D, Shape2D};
+use raylib::prelude::{Color, RaylibDraw, Rectangle, Vector2};
 
 pub struct Circle2D {
     pub position: Vector2,
     pub radius: f32,
     pub colliding: bool,
+    pub layer: CollisionLayer,
+    pub mask: CollisionLayer,
 }
 
 impl Circle2D {
@@ -12,6 +15,8 @@ impl Circle2D {
             position: Vector2::new(0.0, 0.0),
             radius,
             colliding: false,
+            layer: CollisionLayer::DEFAULT,
+            mask: CollisionLayer::ALL,
         }
     }
 
@@ -21,6 +26,16 @@ impl Circle2D {
         self
     }
 
+    pub const fn with_layer(mut self, layer: CollisionLayer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    pub const fn with_mask(mut self, mask: CollisionLayer) -> Self {
+        self.mask = mask;
+        self
+    }
+
     /// Reset colliding
     pub fn reset_colliding(&mut self) {
         self.colliding = false;
@@ -30,34 +45,26 @@ impl Circle2D {
 impl Circle2D {
     /// Circle vs Circle collision check
     pub fn check(&self, circle2: &Circle2D) -> bool {
-        (circle2.position.x - self.position.x).powi(2)
-            + (circle2.position.y - self.position.y).powi(2)
-            <= (self.radius + circle2.radius).powi(2)
+        self.detects(circle2)
+            && (circle2.position.x - self.position.x).powi(2)
+                + (circle2.position.y - self.position.y).powi(2)
+                <= (self.radius + circle2.radius).powi(2)
     }
 
     /// Circle vs Circle collision resolution
-    pub fn collide(&mut self, circle2: &mut Circle2D) -> bool {
-        if self.check(circle2) {
-            // get offset
-            let displacement = Vector2::new(
-                circle2.position.x - self.position.x,
-                circle2.position.y - self.position.y,
-            );
-            let radii_sum = self.radius + circle2.radius;
-            let length = self.position.distance_to(circle2.position);
-            let unit = displacement / length;
-
+    pub fn collide(&mut self, circle2: &mut Circle2D) -> Option<Contact2D> {
+        if let Some(contact) = self.contact(circle2) {
             // resolve circle1 position
-            self.position = circle2.position - unit * radii_sum;
+            self.position += contact.separation();
 
             // set colliding
             self.colliding = true;
             circle2.colliding = true;
-            true
+            Some(contact)
         } else {
             self.colliding = false;
             circle2.colliding = false;
-            false
+            None
         }
     }
 }
@@ -76,3 +83,26 @@ impl Circle2D {
         raylib.draw_circle_v(self.position, 10.0, color.fade(0.5));
     }
 }
+
+impl CollisionObject for Circle2D {
+    fn shape(&self) -> Shape2D<'_> {
+        Shape2D::Circle(self)
+    }
+
+    fn layer(&self) -> CollisionLayer {
+        self.layer
+    }
+
+    fn mask(&self) -> CollisionLayer {
+        self.mask
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.position.x - self.radius,
+            self.position.y - self.radius,
+            self.radius * 2.0,
+            self.radius * 2.0,
+        )
+    }
+}
@@ -0,0 +1,47 @@
+use raylib::prelude::Vector2;
+
+/// Narrow phase result shared by every shape pair
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contact2D {
+    /// Unit normal pointing out of the second shape, towards the first
+    pub normal: Vector2,
+    /// Penetration depth along the normal
+    /// (ray contacts: distance from the ray position to the hit)
+    pub depth: f32,
+    /// Contact points in world space
+    pub points: Vec<Vector2>,
+}
+
+impl Contact2D {
+    pub fn new(normal: Vector2, depth: f32, points: Vec<Vector2>) -> Self {
+        Self {
+            normal,
+            depth,
+            points,
+        }
+    }
+
+    /// Same contact seen from the second shape
+    pub fn flipped(mut self) -> Self {
+        self.normal = -self.normal;
+        self
+    }
+
+    /// Minimum translation vector, moves the first shape out of the second
+    pub fn separation(&self) -> Vector2 {
+        self.normal * self.depth
+    }
+
+    /// Average of all contact points
+    pub fn point(&self) -> Vector2 {
+        if self.points.is_empty() {
+            return Vector2::zero();
+        }
+
+        let mut sum = Vector2::zero();
+        for point in &self.points {
+            sum += *point;
+        }
+        sum / self.points.len() as f32
+    }
+}
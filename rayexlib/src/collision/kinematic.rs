@@ -0,0 +1,475 @@
+use super::{Capsule2D, ColliderInfo, CollisionLayer, CollisionObject, Contact2D, Rect2D, Shape2D};
+use raylib::prelude::{Color, RaylibDraw, Rectangle, Vector2};
+
+/// Kinematic character body, moved by velocity & slid along any collider shape
+pub struct KinematicBody2D {
+    pub collider: Rect2D,
+    /// Steepest surface still counted as floor (radians)
+    pub floor_max_angle: f32,
+    /// Distance the body is pulled down to stay on floors
+    pub snap_length: f32,
+    /// Resolution passes per movement step
+    pub max_slides: usize,
+    /// Collide as the capsule filling the collider box, see `capsule`
+    pub rounded: bool,
+    floor_normal: Option<Vector2>,
+    wall_normal: Option<Vector2>,
+    ceiling_normal: Option<Vector2>,
+    floor_one_way: bool,
+    floor_velocity: Vector2,
+    dropping: bool,
+    crushed: bool,
+    start_bottom: f32,
+    previous_position: Vector2,
+}
+
+impl KinematicBody2D {
+    pub const UP: Vector2 = Vector2::new(0.0, -1.0);
+    pub const DEFAULT_FLOOR_MAX_ANGLE: f32 = std::f32::consts::FRAC_PI_4;
+    pub const DEFAULT_SNAP_LENGTH: f32 = 16.0;
+    pub const DEFAULT_MAX_SLIDES: usize = 4;
+    /// How far above a one way collider's top the body must start to land on it
+    pub const ONE_WAY_TOLERANCE: f32 = 1.0;
+    /// Overlap left after sliding that counts as being crushed
+    pub const CRUSH_DEPTH: f32 = 1.0;
+
+    pub const fn new(collider: Rect2D) -> Self {
+        Self {
+            previous_position: collider.position,
+            collider,
+            floor_max_angle: Self::DEFAULT_FLOOR_MAX_ANGLE,
+            snap_length: Self::DEFAULT_SNAP_LENGTH,
+            max_slides: Self::DEFAULT_MAX_SLIDES,
+            rounded: false,
+            floor_normal: None,
+            wall_normal: None,
+            ceiling_normal: None,
+            floor_one_way: false,
+            floor_velocity: Vector2::new(0.0, 0.0),
+            dropping: false,
+            crushed: false,
+            start_bottom: 0.0,
+        }
+    }
+
+    pub const fn with_floor_max_angle(mut self, floor_max_angle: f32) -> Self {
+        self.floor_max_angle = floor_max_angle;
+        self
+    }
+
+    pub const fn with_snap_length(mut self, snap_length: f32) -> Self {
+        self.snap_length = snap_length;
+        self
+    }
+
+    pub const fn with_max_slides(mut self, max_slides: usize) -> Self {
+        self.max_slides = max_slides;
+        self
+    }
+
+    /// Collide as a capsule, rounded ends glide over seams & ledges
+    pub const fn with_rounded(mut self, rounded: bool) -> Self {
+        self.rounded = rounded;
+        self
+    }
+
+    /// Upright capsule filling the collider box, used when the body is rounded
+    /// (the box stays the body's position & size, following crouches & resizes)
+    pub fn capsule(&self) -> Option<Capsule2D> {
+        if !self.rounded {
+            return None;
+        }
+
+        let center = self.collider.center();
+        let radius = self.collider.size.x.min(self.collider.size.y) / 2.0;
+        Some(
+            Capsule2D::new(radius, self.collider.size.y)
+                .with_position(center.x, center.y)
+                .with_layer(self.collider.layer)
+                .with_mask(self.collider.mask),
+        )
+    }
+
+    pub const fn on_floor(&self) -> bool {
+        self.floor_normal.is_some()
+    }
+
+    /// Standing on a collider that can be dropped through
+    pub const fn on_one_way_floor(&self) -> bool {
+        self.floor_normal.is_some() && self.floor_one_way
+    }
+
+    /// Velocity of the floor carrying the body
+    pub const fn floor_velocity(&self) -> Vector2 {
+        self.floor_velocity
+    }
+
+    /// Squeezed by a crushing collider during the last move
+    pub const fn is_crushed(&self) -> bool {
+        self.crushed
+    }
+
+    /// Currently falling through one way colliders
+    pub const fn is_dropping(&self) -> bool {
+        self.dropping
+    }
+
+    /// Fall through one way colliders until clear of them
+    pub fn drop_through(&mut self) {
+        self.dropping = true;
+    }
+
+    pub const fn on_ceiling(&self) -> bool {
+        self.ceiling_normal.is_some()
+    }
+
+    pub const fn on_wall(&self) -> bool {
+        self.wall_normal.is_some()
+    }
+
+    pub fn on_wall_left(&self) -> bool {
+        self.wall_normal.is_some_and(|normal| normal.x > 0.0)
+    }
+
+    pub fn on_wall_right(&self) -> bool {
+        self.wall_normal.is_some_and(|normal| normal.x < 0.0)
+    }
+
+    /// Angle between floor & up direction (radians), 0 when not on floor
+    pub fn floor_angle(&self) -> f32 {
+        self.floor_normal
+            .map_or(0.0, |normal| normal.dot(Self::UP).clamp(-1.0, 1.0).acos())
+    }
+
+    pub const fn floor_normal(&self) -> Option<Vector2> {
+        self.floor_normal
+    }
+
+    pub const fn wall_normal(&self) -> Option<Vector2> {
+        self.wall_normal
+    }
+
+    pub const fn ceiling_normal(&self) -> Option<Vector2> {
+        self.ceiling_normal
+    }
+
+    /// Direction towards touched sides
+    pub fn direction(&self) -> Vector2 {
+        self.collider.direction()
+    }
+
+    /// Collider position before the last move
+    pub const fn previous_position(&self) -> Vector2 {
+        self.previous_position
+    }
+
+    /// Collider position between the last two moves, for drawing
+    pub fn interpolated_position(&self, alpha: f32) -> Vector2 {
+        self.previous_position.lerp(self.collider.position, alpha)
+    }
+
+    /// Skip interpolating from the current position (e.g. after teleporting)
+    pub fn reset_interpolation(&mut self) {
+        self.previous_position = self.collider.position;
+    }
+
+    pub fn reset_colliding(&mut self) {
+        self.collider.reset_colliding();
+        self.floor_normal = None;
+        self.wall_normal = None;
+        self.ceiling_normal = None;
+        self.floor_one_way = false;
+        self.floor_velocity = Vector2::zero();
+    }
+}
+
+impl KinematicBody2D {
+    /// Move body by `velocity` over `frame_time`, sliding along `colliders`
+    /// Returns the velocity left after sliding (also stored in the collider)
+    pub fn move_and_slide(
+        &mut self,
+        velocity: Vector2,
+        frame_time: f32,
+        colliders: &[&dyn CollisionObject],
+    ) -> Vector2 {
+        self.previous_position = self.collider.position;
+        let was_on_floor = self.on_floor();
+        let platform_velocity = self.floor_velocity;
+        self.reset_colliding();
+
+        // ride along with a moving floor first, kept out of the body's own velocity
+        let carry = platform_velocity * frame_time;
+        if was_on_floor && carry != Vector2::zero() {
+            self.start_bottom = self.collider.position.y + self.collider.size.y + carry.y;
+            self.collider.velocity = platform_velocity;
+            self.collider.position += carry;
+            self.resolve(colliders);
+        }
+
+        self.collider.velocity = velocity;
+        self.start_bottom = self.collider.position.y + self.collider.size.y;
+
+        // steps small enough to never pass through a shape
+        let max_step = self.collider.size.x.min(self.collider.size.y) / 4.0;
+
+        // stop at static box surfaces along the motion path first (continuous),
+        // moving rects have already moved this frame, rounded bodies only step
+        let mut hits: Vec<(usize, f32)> = colliders
+            .iter()
+            .enumerate()
+            .filter_map(|(i, other)| match other.shape() {
+                Shape2D::Rect(rect)
+                    if !self.rounded
+                        && rect.velocity == Vector2::zero()
+                        && !(rect.one_way && self.dropping) =>
+                {
+                    self.collider
+                        .sweep(rect, frame_time)
+                        // low ledges (slope tops, seams) are left to the step
+                        // resolution, which can climb onto them
+                        .filter(|(_, normal)| {
+                            normal.x == 0.0 || rect.position.y < self.start_bottom - max_step
+                        })
+                        .map(|(time, _)| (i, time))
+                }
+                _ => None,
+            })
+            .collect();
+        hits.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        for (i, _) in hits {
+            if let Shape2D::Rect(rect) = colliders[i].shape() {
+                if let Some(contact) = self.collider.collide_rect_swept(rect, frame_time) {
+                    self.add_contact(contact, colliders[i]);
+                }
+            }
+        }
+
+        // move in steps
+        let distance = (self.collider.velocity * frame_time).length();
+        let steps = if max_step > 0.0 {
+            ((distance / max_step).ceil() as usize).max(1)
+        } else {
+            1
+        };
+
+        for _ in 0..steps {
+            self.collider.position += self.collider.velocity * (frame_time / steps as f32);
+            self.resolve(colliders);
+        }
+
+        // keep body on floor when walking down steps & slopes
+        if was_on_floor && !self.on_floor() && self.collider.velocity.y >= 0.0 {
+            // steepest floor drops this far under the horizontal motion
+            let slope_drop =
+                (self.collider.velocity.x * frame_time).abs() * self.floor_max_angle.tan();
+            self.snap_to_floor(colliders, self.snap_length.max(slope_drop));
+        }
+
+        // keep the platform's momentum when leaving it
+        if was_on_floor && !self.on_floor() {
+            self.collider.velocity += platform_velocity;
+        }
+
+        // stop dropping once clear of all one way colliders
+        if self.dropping
+            && !colliders
+                .iter()
+                .any(|other| other.one_way() && self.contact(*other).is_some())
+        {
+            self.dropping = false;
+        }
+
+        self.check_crushed(colliders);
+
+        self.collider.velocity
+    }
+
+    /// Push body out of colliders & remove velocity into their surfaces
+    fn resolve(&mut self, colliders: &[&dyn CollisionObject]) {
+        for _ in 0..self.max_slides {
+            let mut penetrating = false;
+
+            for other in colliders {
+                if let Some(contact) = self.contact(*other) {
+                    if self.passes_through(*other, &contact) {
+                        continue;
+                    }
+
+                    // ignore touched surfaces the body is moving away from
+                    let into = self.collider.velocity.dot(contact.normal);
+                    if contact.depth <= 0.0 && into > 0.0 {
+                        continue;
+                    }
+
+                    let up_dot = contact.normal.dot(Self::UP);
+                    if up_dot >= self.floor_max_angle.cos() {
+                        // push straight up out of floors, so slopes are walked
+                        // without drifting or sliding down them
+                        if contact.depth > 0.0 {
+                            self.collider.position.y -= contact.depth / up_dot;
+                            penetrating = true;
+                        }
+
+                        self.collider.velocity.y = self.collider.velocity.y.min(0.0);
+                    } else {
+                        if contact.depth > 0.0 {
+                            self.collider.position += contact.separation();
+                            penetrating = true;
+                        }
+
+                        // slide along surface
+                        if into < 0.0 {
+                            self.collider.velocity -= contact.normal * into;
+                        }
+                    }
+
+                    self.add_contact(contact, *other);
+                }
+            }
+
+            if !penetrating {
+                break;
+            }
+        }
+    }
+
+    /// Probe down by `distance`, keep the new position only if a floor was found
+    fn snap_to_floor(&mut self, colliders: &[&dyn CollisionObject], distance: f32) {
+        let min_dot = self.floor_max_angle.cos();
+        self.collider.position.y += distance;
+
+        // highest floor below, walls are ignored so the probe can't catch on edges
+        let mut lift = 0.0;
+        let mut floor = None;
+        for other in colliders {
+            let Some(contact) = self.contact(*other) else {
+                continue;
+            };
+
+            let up_dot = contact.normal.dot(Self::UP);
+            if up_dot < min_dot || self.passes_through(*other, &contact) {
+                continue;
+            }
+
+            let floor_lift = contact.depth / up_dot;
+            if floor_lift >= lift {
+                lift = floor_lift;
+                floor = Some((contact, *other));
+            }
+        }
+
+        // lift back up onto the floor, or undo the probe
+        match floor {
+            Some((mut contact, other)) => {
+                self.collider.position.y -= lift;
+                self.collider.velocity.y = self.collider.velocity.y.min(0.0);
+                contact.depth = 0.0;
+                self.add_contact(contact, other);
+            }
+            None => self.collider.position.y -= distance,
+        }
+    }
+
+    /// Still overlapping a crushing collider after sliding means the body is squeezed,
+    /// report it; any other overlap left (e.g. a falling rigid body) is pushed out of
+    fn check_crushed(&mut self, colliders: &[&dyn CollisionObject]) {
+        let overlapping: Vec<&dyn CollisionObject> = colliders
+            .iter()
+            .copied()
+            .filter(|other| {
+                self.contact(*other).is_some_and(|contact| {
+                    contact.depth > Self::CRUSH_DEPTH && !self.passes_through(*other, &contact)
+                })
+            })
+            .collect();
+        self.crushed = overlapping.iter().any(|other| other.crushes());
+
+        for other in overlapping {
+            if other.crushes() {
+                continue;
+            }
+
+            if let Some(contact) = self.contact(other) {
+                self.collider.position += contact.separation();
+            }
+        }
+    }
+
+    /// One way colliders only block bodies landing on them from above
+    fn passes_through(&self, other: &dyn CollisionObject, contact: &Contact2D) -> bool {
+        other.one_way()
+            && (self.dropping
+                || contact.normal.dot(Self::UP) < self.floor_max_angle.cos()
+                || self.start_bottom > other.bounds().y + Self::ONE_WAY_TOLERANCE)
+    }
+
+    /// Sort contact into floor, wall or ceiling
+    fn add_contact(&mut self, contact: Contact2D, other: &dyn CollisionObject) {
+        let min_dot = self.floor_max_angle.cos();
+
+        if contact.normal.dot(Self::UP) >= min_dot {
+            self.floor_normal = Some(contact.normal);
+            self.floor_one_way = other.one_way();
+            self.floor_velocity = other.surface_velocity();
+        } else if contact.normal.dot(-Self::UP) >= min_dot {
+            self.ceiling_normal = Some(contact.normal);
+        } else {
+            self.wall_normal = Some(contact.normal);
+        }
+
+        // one contact per surface direction
+        let contacts = &mut self.collider.contacts;
+        match contacts.iter_mut().find(|c| c.normal == contact.normal) {
+            Some(existing) => *existing = contact,
+            None => contacts.push(contact),
+        }
+    }
+}
+
+impl KinematicBody2D {
+    pub fn draw(&self, color: Color, raylib: &mut impl RaylibDraw) {
+        self.collider.draw(color, raylib);
+        if let Some(capsule) = self.capsule() {
+            capsule.draw(color, raylib);
+        }
+
+        // contact normals
+        for contact in &self.collider.contacts {
+            let point = contact.point();
+            raylib.draw_line_v(point, point + contact.normal * 20.0, color);
+        }
+    }
+}
+
+impl ColliderInfo for KinematicBody2D {
+    fn center(&self) -> Vector2 {
+        self.collider.center()
+    }
+
+    fn velocity(&self) -> Vector2 {
+        self.collider.velocity
+    }
+}
+
+/// Collides as its capsule when rounded, its box otherwise
+impl CollisionObject for KinematicBody2D {
+    fn shape(&self) -> Shape2D<'_> {
+        match self.capsule() {
+            Some(capsule) => Shape2D::Capsule(capsule),
+            None => self.collider.shape(),
+        }
+    }
+
+    fn layer(&self) -> CollisionLayer {
+        self.collider.layer
+    }
+
+    fn mask(&self) -> CollisionLayer {
+        self.collider.mask
+    }
+
+    fn bounds(&self) -> Rectangle {
+        self.shape().bounds()
+    }
+}
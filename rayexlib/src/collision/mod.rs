@@ -1,13 +1,145 @@
+mod alpha_mask;
+mod area;
+mod bitmask;
+mod capsule;
 mod circle;
 mod collider_info;
+mod contact;
+mod kinematic;
+mod layer;
+mod narrow_phase;
+mod platform;
 mod polygon;
+mod query;
 mod ray;
+mod raycast;
 mod rect;
+mod rigid_body;
+mod segment;
+mod spatial_hash;
 
+pub use alpha_mask::AlphaMask;
+pub use area::{Area2D, AreaEvent};
+pub use bitmask::Bitmask2D;
+pub use capsule::Capsule2D;
 pub use circle::Circle2D;
 pub use collider_info::ColliderInfo;
+pub use contact::Contact2D;
+pub use kinematic::KinematicBody2D;
+pub use layer::CollisionLayer;
+pub use platform::{PathMode, Platform2D};
 pub use polygon::Polygon2D;
+pub use query::{overlap, overlaps_any, shape_cast, shape_cast_all, swept_bounds, ShapeHit};
 pub use ray::Ray2D;
+pub use raycast::RayHit;
 pub use rect::Rect2D;
+pub use rigid_body::{BodyShape2D, RigidBody2D};
+pub use segment::Segment2D;
+pub use spatial_hash::SpatialHash;
 
-pub trait CollisionObject {}
+use raylib::prelude::{Rectangle, Vector2};
+
+/// Borrowed view of any collider shape, used by the narrow phase
+#[derive(Clone, Copy)]
+pub enum Shape2D<'a> {
+    Rect(&'a Rect2D),
+    Circle(&'a Circle2D),
+    Polygon(&'a Polygon2D),
+    Ray(&'a Ray2D),
+    /// By value, so bodies can build one from their box on the fly
+    Capsule(Capsule2D),
+    Segment(&'a Segment2D),
+}
+
+impl Shape2D<'_> {
+    pub fn bounds(&self) -> Rectangle {
+        match self {
+            Shape2D::Rect(rect) => rect.bounds(),
+            Shape2D::Circle(circle) => circle.bounds(),
+            Shape2D::Polygon(polygon) => polygon.bounds(),
+            Shape2D::Ray(ray) => ray.bounds(),
+            Shape2D::Capsule(capsule) => capsule.bounds(),
+            Shape2D::Segment(segment) => segment.bounds(),
+        }
+    }
+
+    /// Outline in world space (circles have none, rays are a segment,
+    /// capsules are their core segment, see `radius`),
+    /// convex unless a concave polygon, whose parts the narrow phase uses instead
+    pub fn points(&self) -> Vec<Vector2> {
+        match self {
+            Shape2D::Rect(rect) => narrow_phase::rect_points(rect.position, rect.size),
+            Shape2D::Circle(_) => Vec::new(),
+            Shape2D::Polygon(polygon) => polygon.points().to_vec(),
+            Shape2D::Ray(ray) => vec![ray.position, ray.position + ray.direction],
+            Shape2D::Capsule(capsule) => capsule.points().to_vec(),
+            Shape2D::Segment(segment) => vec![segment.start, segment.end],
+        }
+    }
+
+    /// Contact with `other` regardless of layers, normal points away from `other`
+    pub fn contact(&self, other: Shape2D) -> Option<Contact2D> {
+        narrow_phase::contact(*self, other)
+    }
+
+    /// Rounding added around the outline
+    pub fn radius(&self) -> f32 {
+        match self {
+            Shape2D::Circle(circle) => circle.radius,
+            Shape2D::Capsule(capsule) => capsule.radius,
+            _ => 0.0,
+        }
+    }
+}
+
+pub trait CollisionObject {
+    /// Axis aligned bounding box
+    fn bounds(&self) -> Rectangle;
+
+    /// Shape used for narrow phase tests
+    fn shape(&self) -> Shape2D<'_>;
+
+    /// One way colliders are only solid from above
+    fn one_way(&self) -> bool {
+        false
+    }
+
+    /// Velocity given to bodies standing on this object
+    fn surface_velocity(&self) -> Vector2 {
+        Vector2::zero()
+    }
+
+    /// Driven along a path regardless of what's in the way, so it can crush bodies
+    fn crushes(&self) -> bool {
+        false
+    }
+
+    /// Layers this object is on
+    fn layer(&self) -> CollisionLayer {
+        CollisionLayer::DEFAULT
+    }
+
+    /// Layers this object detects
+    fn mask(&self) -> CollisionLayer {
+        CollisionLayer::ALL
+    }
+
+    /// `other` is on a layer in this object's mask
+    fn detects(&self, other: &dyn CollisionObject) -> bool {
+        self.mask().intersects(other.layer())
+    }
+
+    /// Shape vs Shape contact, normal points away from `other`
+    /// (none if `other` is not detected)
+    fn contact(&self, other: &dyn CollisionObject) -> Option<Contact2D> {
+        if !self.detects(other) {
+            return None;
+        }
+        narrow_phase::contact(self.shape(), other.shape())
+    }
+
+    /// Shape vs Shape collision check
+    fn intersects(&self, other: &dyn CollisionObject) -> bool {
+        self.contact(other).is_some()
+    }
+}
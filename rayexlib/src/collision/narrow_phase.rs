@@ -0,0 +1,585 @@
+use super::{Contact2D, Shape2D};
+use raylib::prelude::{Rectangle, Vector2};
+
+/// Distance a clipped point may sit in front of the reference face
+const CONTACT_TOLERANCE: f32 = 0.01;
+
+/// Contact of shape `a` against shape `b`, normal points towards `a`
+pub fn contact(a: Shape2D, b: Shape2D) -> Option<Contact2D> {
+    contact_translated(a, Vector2::zero(), b)
+}
+
+/// Contact of shape `a` moved by `offset` against shape `b`, normal points towards `a`
+pub fn contact_translated(a: Shape2D, offset: Vector2, b: Shape2D) -> Option<Contact2D> {
+    use Shape2D::{Capsule, Circle, Polygon, Ray, Segment};
+
+    // concave polygons collide as their convex parts
+    let nearest = matches!(a, Ray(_)) || matches!(b, Ray(_));
+    if let Polygon(polygon) = a {
+        if polygon.is_concave() {
+            let contacts = polygon
+                .parts()
+                .iter()
+                .filter_map(|part| contact_translated(Polygon(part), offset, b));
+            return merged_contact(contacts, nearest);
+        }
+    }
+    if let Polygon(polygon) = b {
+        if polygon.is_concave() {
+            let contacts = polygon
+                .parts()
+                .iter()
+                .filter_map(|part| contact_translated(a, offset, Polygon(part)));
+            return merged_contact(contacts, nearest);
+        }
+    }
+
+    match (a, b) {
+        // rays
+        (Ray(ray), Ray(ray2)) => ray_ray(
+            ray.position + offset,
+            ray.direction,
+            ray2.position,
+            ray2.direction,
+        ),
+        (Ray(ray), other) => ray_shape(ray.position + offset, ray.direction, other),
+        // moving the ray back is the same as moving the shape forward
+        (a, Ray(ray)) => ray_shape(ray.position - offset, ray.direction, a)
+            .map(|contact| moved_contact(contact, offset).flipped()),
+
+        // areas
+        (a, b) if !overlaps(&translated_bounds(a.bounds(), offset), &b.bounds()) => None,
+        (Circle(c1), Circle(c2)) => {
+            circle_circle(c1.position + offset, c1.radius, c2.position, c2.radius)
+        }
+        (a, b) if matches!(a, Capsule(_) | Segment(_)) || matches!(b, Capsule(_) | Segment(_)) => {
+            rounded_convex(
+                &core(a, offset),
+                a.radius(),
+                &core(b, Vector2::zero()),
+                b.radius(),
+            )
+        }
+        (Circle(c), other) => circle_convex(c.position + offset, c.radius, &other.points()),
+        (other, Circle(c)) => {
+            circle_convex(c.position, c.radius, &translated(other, offset)).map(Contact2D::flipped)
+        }
+        (a, b) => convex_convex(&translated(a, offset), &b.points()),
+    }
+}
+
+/// Nearest contact for rays, deepest otherwise
+fn merged_contact(contacts: impl Iterator<Item = Contact2D>, nearest: bool) -> Option<Contact2D> {
+    if nearest {
+        contacts.min_by(|a, b| a.depth.total_cmp(&b.depth))
+    } else {
+        contacts.max_by(|a, b| a.depth.total_cmp(&b.depth))
+    }
+}
+
+/// Convex outline of `shape` moved by `offset`
+fn translated(shape: Shape2D, offset: Vector2) -> Vec<Vector2> {
+    let mut points = shape.points();
+    for point in &mut points {
+        *point += offset;
+    }
+    points
+}
+
+/// Outline without rounding (circles are their center) moved by `offset`
+fn core(shape: Shape2D, offset: Vector2) -> Vec<Vector2> {
+    match shape {
+        Shape2D::Circle(circle) => vec![circle.position + offset],
+        shape => translated(shape, offset),
+    }
+}
+
+fn moved_contact(mut contact: Contact2D, offset: Vector2) -> Contact2D {
+    for point in &mut contact.points {
+        *point += offset;
+    }
+    contact
+}
+
+fn translated_bounds(bounds: Rectangle, offset: Vector2) -> Rectangle {
+    Rectangle::new(
+        bounds.x + offset.x,
+        bounds.y + offset.y,
+        bounds.width,
+        bounds.height,
+    )
+}
+
+/// Ray segment against any non ray shape
+fn ray_shape(origin: Vector2, direction: Vector2, shape: Shape2D) -> Option<Contact2D> {
+    match shape {
+        Shape2D::Circle(c) => ray_circle(origin, direction, c.position, c.radius),
+        Shape2D::Segment(s) => ray_ray(origin, direction, s.start, s.end - s.start),
+        Shape2D::Capsule(c) => ray_capsule(origin, direction, c.points(), c.radius),
+        shape => ray_convex(origin, direction, &shape.points()),
+    }
+}
+
+/// Inclusive aabb overlap, touching boxes count as overlapping
+pub fn overlaps(a: &Rectangle, b: &Rectangle) -> bool {
+    a.x <= b.x + b.width && b.x <= a.x + a.width && a.y <= b.y + b.height && b.y <= a.y + a.height
+}
+
+/// Corners of a rectangle, clockwise
+pub fn rect_points(position: Vector2, size: Vector2) -> Vec<Vector2> {
+    vec![
+        position,
+        Vector2::new(position.x + size.x, position.y),
+        position + size,
+        Vector2::new(position.x, position.y + size.y),
+    ]
+}
+
+fn centroid(points: &[Vector2]) -> Vector2 {
+    let mut sum = Vector2::zero();
+    for point in points {
+        sum += *point;
+    }
+    sum / points.len() as f32
+}
+
+/// Min & max of points projected on axis
+fn project(points: &[Vector2], axis: Vector2) -> (f32, f32) {
+    let mut min = f32::INFINITY;
+    let mut max = f32::NEG_INFINITY;
+    for point in points {
+        let dot_product = point.dot(axis);
+        min = min.min(dot_product);
+        max = max.max(dot_product);
+    }
+    (min, max)
+}
+
+/// Minimum translation of interval a out of interval b,
+/// with the direction (+1 / -1) along the projected axis
+fn separation(min_a: f32, max_a: f32, min_b: f32, max_b: f32) -> (f32, f32) {
+    let push_negative = max_a - min_b;
+    let push_positive = max_b - min_a;
+
+    if push_negative < push_positive {
+        (push_negative, -1.0)
+    } else {
+        (push_positive, 1.0)
+    }
+}
+
+/// Unit normals of every edge (direction not guaranteed)
+fn edge_normals(points: &[Vector2]) -> impl Iterator<Item = Vector2> + '_ {
+    (0..points.len()).filter_map(|i| {
+        let edge = points[(i + 1) % points.len()] - points[i];
+        let normal = Vector2::new(-edge.y, edge.x).normalized();
+        (normal != Vector2::zero()).then_some(normal)
+    })
+}
+
+fn circle_circle(p1: Vector2, r1: f32, p2: Vector2, r2: f32) -> Option<Contact2D> {
+    let displacement = p1 - p2;
+    let distance = displacement.length();
+    let radii_sum = r1 + r2;
+
+    if distance > radii_sum {
+        return None;
+    }
+
+    // concentric circles, push up
+    let normal = if distance == 0.0 {
+        Vector2::new(0.0, -1.0)
+    } else {
+        displacement / distance
+    };
+
+    Some(Contact2D::new(
+        normal,
+        radii_sum - distance,
+        vec![p2 + normal * r2],
+    ))
+}
+
+/// Separating axis test of circle against convex points
+fn circle_convex(center: Vector2, radius: f32, points: &[Vector2]) -> Option<Contact2D> {
+    // extra axis from closest vertex to circle center
+    let closest = points
+        .iter()
+        .copied()
+        .min_by(|a, b| a.distance_to(center).total_cmp(&b.distance_to(center)))?;
+    let vertex_axis = (center - closest).normalized();
+
+    let mut depth = f32::INFINITY;
+    let mut normal = Vector2::zero();
+
+    for axis in edge_normals(points).chain((vertex_axis != Vector2::zero()).then_some(vertex_axis))
+    {
+        let (min_poly, max_poly) = project(points, axis);
+        let c = center.dot(axis);
+        let (min_circle, max_circle) = (c - radius, c + radius);
+
+        // found separating axis
+        if max_circle < min_poly || max_poly < min_circle {
+            return None;
+        }
+
+        // shortest push of circle out of polygon along this axis, either way
+        let (overlap, direction) = separation(min_circle, max_circle, min_poly, max_poly);
+        if overlap < depth {
+            depth = overlap;
+            normal = axis * direction;
+        }
+    }
+
+    Some(Contact2D::new(
+        normal,
+        depth,
+        vec![center - normal * radius],
+    ))
+}
+
+/// Separating axis test of two convex point sets, with clipped contact points
+fn convex_convex(a: &[Vector2], b: &[Vector2]) -> Option<Contact2D> {
+    let mut depth = f32::INFINITY;
+    let mut normal = Vector2::zero();
+
+    for axis in edge_normals(a).chain(edge_normals(b)) {
+        let (min_a, max_a) = project(a, axis);
+        let (min_b, max_b) = project(b, axis);
+
+        // found separating axis
+        if max_a < min_b || max_b < min_a {
+            return None;
+        }
+
+        // shortest push of a out of b along this axis, either way
+        let (overlap, direction) = separation(min_a, max_a, min_b, max_b);
+        if overlap < depth {
+            depth = overlap;
+            normal = axis * direction;
+        }
+    }
+
+    let points = clip_contact_points(a, b, normal);
+    Some(Contact2D::new(normal, depth, points))
+}
+
+/// Edge of polygon most facing `direction` (start, end, furthest vertex)
+fn best_edge(points: &[Vector2], direction: Vector2) -> (Vector2, Vector2, Vector2) {
+    let count = points.len();
+
+    // furthest vertex along direction
+    let mut index = 0;
+    for i in 1..count {
+        if points[i].dot(direction) > points[index].dot(direction) {
+            index = i;
+        }
+    }
+
+    let vertex = points[index];
+    let next = points[(index + 1) % count];
+    let prev = points[(index + count - 1) % count];
+
+    // pick the neighbour edge most perpendicular to direction
+    let to_next = (next - vertex).normalized();
+    let to_prev = (prev - vertex).normalized();
+    if to_prev.dot(direction).abs() <= to_next.dot(direction).abs() {
+        (prev, vertex, vertex)
+    } else {
+        (vertex, next, vertex)
+    }
+}
+
+/// Keep points where `dot(direction, point) >= offset`, splitting the segment
+fn clip(v1: Vector2, v2: Vector2, direction: Vector2, offset: f32) -> Vec<Vector2> {
+    let d1 = direction.dot(v1) - offset;
+    let d2 = direction.dot(v2) - offset;
+
+    let mut points = Vec::with_capacity(2);
+    if d1 >= 0.0 {
+        points.push(v1);
+    }
+    if d2 >= 0.0 {
+        points.push(v2);
+    }
+    if d1 * d2 < 0.0 {
+        points.push(v1 + (v2 - v1) * (d1 / (d1 - d2)));
+    }
+    points
+}
+
+/// Reference & incident edge clipping
+fn clip_contact_points(a: &[Vector2], b: &[Vector2], normal: Vector2) -> Vec<Vector2> {
+    // a faces b along -normal, b faces a along normal
+    let edge_a = best_edge(a, -normal);
+    let edge_b = best_edge(b, normal);
+
+    // reference edge is the one most perpendicular to the normal
+    let dir_a = (edge_a.1 - edge_a.0).normalized();
+    let dir_b = (edge_b.1 - edge_b.0).normalized();
+    let (reference, incident, face_normal) = if dir_a.dot(normal).abs() <= dir_b.dot(normal).abs() {
+        (edge_a, edge_b, -normal)
+    } else {
+        (edge_b, edge_a, normal)
+    };
+
+    // clip incident edge to the side planes of reference edge
+    let side = (reference.1 - reference.0).normalized();
+    let clipped = clip(incident.0, incident.1, side, side.dot(reference.0));
+    if clipped.len() < 2 {
+        return vec![deepest_point(a, normal)];
+    }
+    let clipped = clip(clipped[0], clipped[1], -side, -side.dot(reference.1));
+    if clipped.len() < 2 {
+        return vec![deepest_point(a, normal)];
+    }
+
+    // keep points behind reference face
+    let face = face_normal.dot(reference.2);
+    let points: Vec<Vector2> = clipped
+        .into_iter()
+        .filter(|point| face_normal.dot(*point) - face <= CONTACT_TOLERANCE)
+        .collect();
+
+    if points.is_empty() {
+        vec![deepest_point(a, normal)]
+    } else {
+        points
+    }
+}
+
+/// Fallback contact, vertex of a furthest into b
+fn deepest_point(a: &[Vector2], normal: Vector2) -> Vector2 {
+    a.iter()
+        .copied()
+        .min_by(|p1, p2| p1.dot(normal).total_cmp(&p2.dot(normal)))
+        .unwrap_or_else(Vector2::zero)
+}
+
+/// Segment intersection, normal faces the first ray's position
+fn ray_ray(o1: Vector2, d1: Vector2, o2: Vector2, d2: Vector2) -> Option<Contact2D> {
+    let denominator = d1.x * d2.y - d1.y * d2.x;
+
+    // parallel rays never cross
+    if denominator == 0.0 {
+        return None;
+    }
+
+    let offset = o2 - o1;
+    let t = (offset.x * d2.y - offset.y * d2.x) / denominator;
+    let u = (offset.x * d1.y - offset.y * d1.x) / denominator;
+
+    if !(0.0..=1.0).contains(&t) || !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let mut normal = Vector2::new(-d2.y, d2.x).normalized();
+    if normal.dot(d1) > 0.0 {
+        normal = -normal;
+    }
+
+    let point = o1 + d1 * t;
+    Some(Contact2D::new(normal, d1.length() * t, vec![point]))
+}
+
+fn ray_circle(
+    origin: Vector2,
+    direction: Vector2,
+    center: Vector2,
+    radius: f32,
+) -> Option<Contact2D> {
+    let offset = origin - center;
+
+    // ray starts inside circle
+    if offset.length() <= radius {
+        return Some(Contact2D::new(offset.normalized(), 0.0, vec![origin]));
+    }
+
+    // solve |offset + direction * t| = radius
+    let a = direction.dot(direction);
+    let b = 2.0 * offset.dot(direction);
+    let c = offset.dot(offset) - radius * radius;
+    let discriminant = b * b - 4.0 * a * c;
+
+    if a == 0.0 || discriminant < 0.0 {
+        return None;
+    }
+
+    let t = (-b - discriminant.sqrt()) / (2.0 * a);
+    if !(0.0..=1.0).contains(&t) {
+        return None;
+    }
+
+    let point = origin + direction * t;
+    Some(Contact2D::new(
+        (point - center).normalized(),
+        direction.length() * t,
+        vec![point],
+    ))
+}
+
+/// Cyrus-Beck clipping of ray segment against convex points
+fn ray_convex(origin: Vector2, direction: Vector2, points: &[Vector2]) -> Option<Contact2D> {
+    let center = centroid(points);
+    let mut t_enter = f32::NEG_INFINITY;
+    let mut t_exit = 1.0_f32;
+    let mut normal = Vector2::zero();
+
+    for i in 0..points.len() {
+        let start = points[i];
+        let edge = points[(i + 1) % points.len()] - start;
+
+        // outward edge normal
+        let mut edge_normal = Vector2::new(-edge.y, edge.x).normalized();
+        if edge_normal == Vector2::zero() {
+            continue;
+        }
+        if edge_normal.dot(center - start) > 0.0 {
+            edge_normal = -edge_normal;
+        }
+
+        let numerator = edge_normal.dot(start - origin);
+        let denominator = edge_normal.dot(direction);
+
+        if denominator == 0.0 {
+            // parallel & outside edge
+            if numerator < 0.0 {
+                return None;
+            }
+        } else if denominator < 0.0 {
+            // entering edge
+            let t = numerator / denominator;
+            if t > t_enter {
+                t_enter = t;
+                normal = edge_normal;
+            }
+        } else {
+            // exiting edge
+            t_exit = t_exit.min(numerator / denominator);
+        }
+    }
+
+    // missed, or shape is behind ray
+    if t_enter > t_exit || t_exit < 0.0 {
+        return None;
+    }
+
+    // ray starts inside shape
+    if t_enter < 0.0 {
+        return Some(Contact2D::new(-direction.normalized(), 0.0, vec![origin]));
+    }
+
+    let point = origin + direction * t_enter;
+    Some(Contact2D::new(
+        normal,
+        direction.length() * t_enter,
+        vec![point],
+    ))
+}
+
+/// Nearest hit of a ray segment against either end circle or the body of a capsule
+fn ray_capsule(
+    origin: Vector2,
+    direction: Vector2,
+    [top, bottom]: [Vector2; 2],
+    radius: f32,
+) -> Option<Contact2D> {
+    let side = (bottom - top).normalized();
+    let side = Vector2::new(-side.y, side.x) * radius;
+    let body = (top != bottom).then(|| {
+        ray_convex(
+            origin,
+            direction,
+            &[top + side, bottom + side, bottom - side, top - side],
+        )
+    });
+
+    [
+        ray_circle(origin, direction, top, radius),
+        ray_circle(origin, direction, bottom, radius),
+        body.flatten(),
+    ]
+    .into_iter()
+    .flatten()
+    .min_by(|a, b| a.depth.total_cmp(&b.depth))
+}
+
+/// Closest pair of points between two convex cores (on a, on b),
+/// only meaningful while the cores don't overlap
+fn closest_points(a: &[Vector2], b: &[Vector2]) -> (Vector2, Vector2) {
+    let mut closest = (a[0], b[0]);
+    let mut distance = f32::INFINITY;
+
+    let mut check = |point_a: Vector2, point_b: Vector2| {
+        let d = point_a.distance_to(point_b);
+        if d < distance {
+            distance = d;
+            closest = (point_a, point_b);
+        }
+    };
+
+    for i in 0..b.len() {
+        let (start, end) = (b[i], b[(i + 1) % b.len()]);
+        for point in a {
+            check(*point, closest_on_segment(*point, start, end));
+        }
+    }
+    for i in 0..a.len() {
+        let (start, end) = (a[i], a[(i + 1) % a.len()]);
+        for point in b {
+            check(closest_on_segment(*point, start, end), *point);
+        }
+    }
+    closest
+}
+
+/// Closest point on the segment from `start` to `end` to `point`
+pub fn closest_on_segment(point: Vector2, start: Vector2, end: Vector2) -> Vector2 {
+    let edge = end - start;
+    let length_sqr = edge.dot(edge);
+    if length_sqr == 0.0 {
+        return start;
+    }
+    start + edge * ((point - start).dot(edge) / length_sqr).clamp(0.0, 1.0)
+}
+
+/// Convex cores grown by a radius (capsules, segments & circles against other shapes)
+fn rounded_convex(a: &[Vector2], radius_a: f32, b: &[Vector2], radius_b: f32) -> Option<Contact2D> {
+    let radii = radius_a + radius_b;
+
+    // points & segments have no inside, only shapes with area can contain the other core
+    let cores_overlap = if a.len() > 2 || b.len() > 2 {
+        convex_convex(a, b).is_some()
+    } else if a.len() == 2 && b.len() == 2 {
+        ray_ray(a[0], a[1] - a[0], b[0], b[1] - b[0]).is_some()
+    } else {
+        false
+    };
+
+    let (point_a, point_b) = closest_points(a, b);
+    let offset = point_a - point_b;
+    let distance = offset.length();
+
+    // cores apart, rounded outlines may still touch
+    if !cores_overlap && distance > f32::EPSILON {
+        if distance > radii {
+            return None;
+        }
+
+        let normal = offset / distance;
+        return Some(Contact2D::new(
+            normal,
+            radii - distance,
+            vec![point_b + normal * radius_b],
+        ));
+    }
+
+    // cores overlap, push out along the shallowest axis
+    let mut contact = convex_convex(a, b)?;
+    if contact.normal == Vector2::zero() {
+        // concentric points share no axis, push up
+        contact = Contact2D::new(Vector2::new(0.0, -1.0), 0.0, vec![point_b]);
+    }
+    contact.depth += radii;
+    Some(contact)
+}
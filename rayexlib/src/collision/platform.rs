@@ -0,0 +1,209 @@
+use super::{CollisionLayer, CollisionObject, Rect2D, Shape2D};
+use raylib::prelude::{Color, RaylibDraw, Rectangle, Vector2};
+
+/// What a platform does after reaching its last waypoint
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PathMode {
+    /// Head back to the first waypoint
+    #[default]
+    Loop,
+    /// Walk the waypoints in reverse
+    PingPong,
+    /// Stop at the last waypoint
+    Once,
+}
+
+/// Kinematic rect moving along waypoints (collider positions),
+/// bodies standing on it are carried by its velocity
+pub struct Platform2D {
+    pub collider: Rect2D,
+    pub waypoints: Vec<Vector2>,
+    pub speed: f32,
+    pub mode: PathMode,
+    /// Seconds to wait at each waypoint
+    pub wait_time: f32,
+    target: usize,
+    reverse: bool,
+    waiting: f32,
+    previous_position: Vector2,
+}
+
+impl Platform2D {
+    /// Platform starting at the first waypoint
+    pub fn new(collider: Rect2D, waypoints: Vec<Vector2>, speed: f32) -> Self {
+        // confirm valid path
+        assert!(!waypoints.is_empty());
+
+        let mut collider = collider;
+        collider.position = waypoints[0];
+
+        Self {
+            previous_position: collider.position,
+            collider,
+            target: (waypoints.len() > 1) as usize,
+            waypoints,
+            speed,
+            mode: PathMode::Loop,
+            wait_time: 0.0,
+            reverse: false,
+            waiting: 0.0,
+        }
+    }
+
+    pub const fn with_mode(mut self, mode: PathMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub const fn with_wait_time(mut self, wait_time: f32) -> Self {
+        self.wait_time = wait_time;
+        self
+    }
+
+    /// Waypoint currently moved towards
+    pub fn target(&self) -> Vector2 {
+        self.waypoints[self.target]
+    }
+
+    /// Reached the end of a `PathMode::Once` path
+    pub fn is_finished(&self) -> bool {
+        self.mode == PathMode::Once
+            && self.target == self.waypoints.len() - 1
+            && self.collider.position == self.target()
+    }
+
+    /// Move towards the target waypoint, velocity is set to this frame's motion
+    pub fn update(&mut self, frame_time: f32) {
+        let start = self.collider.position;
+        self.previous_position = start;
+        self.collider.velocity = Vector2::zero();
+
+        // wait at waypoint
+        if self.waiting > 0.0 {
+            self.waiting -= frame_time;
+            return;
+        }
+
+        // move without overshooting the waypoint
+        let mut travel = self.speed * frame_time;
+        for _ in 0..self.waypoints.len() {
+            if travel <= 0.0 || self.waypoints.len() < 2 || self.is_finished() {
+                break;
+            }
+
+            let to_target = self.target() - self.collider.position;
+            let distance = to_target.length();
+
+            if distance > travel {
+                self.collider.position += to_target / distance * travel;
+                break;
+            }
+
+            // arrived
+            self.collider.position = self.target();
+            travel -= distance;
+            self.next_target();
+
+            if self.wait_time > 0.0 {
+                self.waiting = self.wait_time;
+                break;
+            }
+        }
+
+        if frame_time > 0.0 {
+            self.collider.velocity = (self.collider.position - start) / frame_time;
+        }
+    }
+
+    /// Collider position between the last two updates, for drawing
+    pub fn interpolated_position(&self, alpha: f32) -> Vector2 {
+        self.previous_position.lerp(self.collider.position, alpha)
+    }
+
+    /// Draw at the current position until the next update
+    pub fn reset_interpolation(&mut self) {
+        self.previous_position = self.collider.position;
+    }
+
+    fn next_target(&mut self) {
+        let last = self.waypoints.len() - 1;
+        if last == 0 {
+            return;
+        }
+
+        self.target = match self.mode {
+            PathMode::Loop => (self.target + 1) % self.waypoints.len(),
+            PathMode::Once => (self.target + 1).min(last),
+            PathMode::PingPong => {
+                if self.target == last {
+                    self.reverse = true;
+                } else if self.target == 0 {
+                    self.reverse = false;
+                }
+
+                if self.reverse {
+                    self.target - 1
+                } else {
+                    self.target + 1
+                }
+            }
+        };
+    }
+}
+
+impl Platform2D {
+    pub fn draw(&self, color: Color, raylib: &mut impl RaylibDraw) {
+        self.collider.draw(color, raylib);
+    }
+
+    /// Draw between the last two updates
+    pub fn draw_interpolated(&self, alpha: f32, color: Color, raylib: &mut impl RaylibDraw) {
+        self.collider
+            .draw_at(self.interpolated_position(alpha), color, raylib);
+    }
+
+    /// Draw path between waypoint centers
+    pub fn draw_path(&self, color: Color, raylib: &mut impl RaylibDraw) {
+        let offset = self.collider.size / 2.0;
+        for (i, point) in self.waypoints.iter().enumerate() {
+            raylib.draw_circle_v(*point + offset, 5.0, color);
+
+            let next = match self.mode {
+                PathMode::Loop => self.waypoints[(i + 1) % self.waypoints.len()],
+                _ if i + 1 < self.waypoints.len() => self.waypoints[i + 1],
+                _ => break,
+            };
+            raylib.draw_line_v(*point + offset, next + offset, color.fade(0.5));
+        }
+    }
+}
+
+impl CollisionObject for Platform2D {
+    fn shape(&self) -> Shape2D<'_> {
+        self.collider.shape()
+    }
+
+    fn bounds(&self) -> Rectangle {
+        self.collider.bounds()
+    }
+
+    fn one_way(&self) -> bool {
+        self.collider.one_way
+    }
+
+    fn layer(&self) -> CollisionLayer {
+        self.collider.layer
+    }
+
+    fn mask(&self) -> CollisionLayer {
+        self.collider.mask
+    }
+
+    fn surface_velocity(&self) -> Vector2 {
+        self.collider.velocity
+    }
+
+    fn crushes(&self) -> bool {
+        true
+    }
+}
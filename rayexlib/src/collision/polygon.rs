@@ -1,16 +1,22 @@
-use raylib::prelude::{Color, RaylibDraw, Vector2};
+use super::{CollisionLayer, CollisionObject, Contact2D, Shape2D};
+use raylib::prelude::{Color, RaylibDraw, Rectangle, Vector2};
 
 #[derive(Clone)]
 pub struct Polygon2D {
     pub position: Vector2,
     pub rotation: f32,
     pub velocity: Vector2,
+    pub one_way: bool,
+    pub layer: CollisionLayer,
+    pub mask: CollisionLayer,
     points: Vec<Vector2>,
     model_points: Vec<Vector2>,
     broad_collider_radius: f32,
     sides: usize,
     centroid: Vector2,
     colliding: bool,
+    /// Convex pieces of a concave polygon, empty when convex
+    parts: Vec<Polygon2D>,
 }
 
 impl Polygon2D {
@@ -42,16 +48,21 @@ impl Polygon2D {
             position: Vector2::zero(),
             rotation: 0.0,
             velocity: Vector2::zero(),
+            one_way: false,
+            layer: CollisionLayer::DEFAULT,
+            mask: CollisionLayer::ALL,
             points: points.clone(),
             model_points: points,
             broad_collider_radius: size * std::f32::consts::SQRT_2 / 2.0,
             sides,
             centroid,
             colliding: false,
+            parts: Vec::new(),
         }
     }
 
     /// Construct a polygon from a set of points [center at (0,0)]
+    /// Concave outlines are split into convex parts for collision
     pub fn from(model_points: Vec<Vector2>) -> Polygon2D {
         // confirm valid shape
         assert!(model_points.len() > 1);
@@ -74,19 +85,87 @@ impl Polygon2D {
             }
         }
 
+        // split concave outlines, parts share the polygon's transform
+        let decomposition = convex_decomposition(&model_points);
+        let parts = if decomposition.len() > 1 {
+            decomposition
+                .into_iter()
+                .map(|part| Polygon2D::from(part.iter().map(|&i| model_points[i]).collect()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         Polygon2D {
             position: Vector2::zero(),
             rotation: 0.0,
             velocity: Vector2::zero(),
+            one_way: false,
+            layer: CollisionLayer::DEFAULT,
+            mask: CollisionLayer::ALL,
             points: model_points.clone(),
             model_points: model_points.clone(),
             broad_collider_radius: centroid.distance_to(farthest_point),
             sides: model_points.len(),
             centroid,
             colliding: false,
+            parts,
         }
     }
 
+    /// Construct a right triangle tile [bounds center at (0,0)]
+    /// Slope rises to the right, or to the left if `rise_left`
+    pub fn right_triangle(size: Vector2, rise_left: bool) -> Polygon2D {
+        let half = size / 2.0;
+        let peak_x = if rise_left { -half.x } else { half.x };
+
+        Polygon2D::from(vec![
+            Vector2::new(-half.x, half.y),
+            Vector2::new(half.x, half.y),
+            Vector2::new(peak_x, -half.y),
+        ])
+    }
+
+    pub fn with_position(mut self, x: f32, y: f32) -> Self {
+        self.position = Vector2::new(x, y);
+        self.update_transform();
+        self
+    }
+
+    pub const fn with_one_way(mut self) -> Self {
+        self.one_way = true;
+        self
+    }
+
+    pub const fn with_layer(mut self, layer: CollisionLayer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    pub const fn with_mask(mut self, mask: CollisionLayer) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Transformed points in world space
+    pub fn points(&self) -> &[Vector2] {
+        &self.points
+    }
+
+    /// Untransformed points relative to position
+    pub fn model_points(&self) -> &[Vector2] {
+        &self.model_points
+    }
+
+    /// Convex pieces used for collision, empty if the polygon is convex
+    pub fn parts(&self) -> &[Polygon2D] {
+        &self.parts
+    }
+
+    pub fn is_concave(&self) -> bool {
+        !self.parts.is_empty()
+    }
+
     /// return colliding
     pub fn is_colliding(&self) -> bool {
         self.colliding
@@ -111,12 +190,17 @@ impl Polygon2D {
                 + (self.model_points[i].y * theta_cos)
                 + (self.position.y);
         }
+
+        for part in &mut self.parts {
+            part.position = self.position;
+            part.rotation = self.rotation;
+            part.update_transform();
+        }
     }
 }
 
 impl Polygon2D {
-    /// Only works for convex polygons (SAT)
-    /// Collision detection begins to break down at ~50 sides
+    /// SAT, concave polygons are checked part by part
     pub fn check(&self, polygon2: &Polygon2D) -> bool {
         // if polygon not in broad range, return false
         if self.position.distance_to(polygon2.position)
@@ -125,127 +209,44 @@ impl Polygon2D {
             return false;
         }
 
-        let mut poly1 = &self;
-        let mut poly2 = &polygon2;
-
-        for shape in 0..2 {
-            // switch poly namespaces
-            if shape == 1 {
-                poly1 = &polygon2;
-                poly2 = &self;
-            }
-
-            for i in 0..poly1.sides {
-                // index of next point
-                let next_i = (i + 1) % poly1.sides;
-                // get axis projection for side
-                let axis_proj = Vector2 {
-                    x: -(poly1.points[next_i].y - poly1.points[i].y),
-                    y: poly1.points[next_i].x - poly1.points[i].x,
-                };
-                // find min and max 1D points for poly1
-                let mut min_poly1 = f32::INFINITY;
-                let mut max_poly1 = f32::NEG_INFINITY;
-                for p in 0..poly1.sides {
-                    let dot_product = poly1.points[p].dot(axis_proj);
-                    min_poly1 = min_poly1.min(dot_product);
-                    max_poly1 = max_poly1.max(dot_product);
-                }
-                // find min and max 1D points for poly2
-                let mut min_poly2 = f32::INFINITY;
-                let mut max_poly2 = f32::NEG_INFINITY;
-                for p in 0..poly2.sides {
-                    let dot_product = poly2.points[p].dot(axis_proj);
-                    min_poly2 = min_poly2.min(dot_product);
-                    max_poly2 = max_poly2.max(dot_product);
-                }
-                // check if poly min/max values dont overlap
-                if !(max_poly2 >= min_poly1 && max_poly1 >= min_poly2) {
-                    return false;
-                }
-            }
-        }
-
-        return true;
+        self.intersects(polygon2)
     }
 
-    /// Only works for convex polygons
-    /// Collision resolution begins to break down at ~50 sides
-    pub fn collide(&mut self, polygon2: &mut Polygon2D) -> bool {
-        // if polygon not in broad range, return false
+    /// Displaces poly1 along the minimum translation vector (SAT)
+    /// Concave polygons are pushed out of their deepest overlapping part
+    pub fn collide(&mut self, polygon2: &mut Polygon2D) -> Option<Contact2D> {
+        // if polygon not in broad range, return none
         if self.position.distance_to(polygon2.position)
             > self.broad_collider_radius + polygon2.broad_collider_radius
         {
             self.colliding = false;
-            return false;
-        }
-
-        let mut poly1 = &self;
-        let mut poly2 = &polygon2;
-        let mut overlap = f32::INFINITY;
-
-        for shape in 0..2 {
-            // switch poly namespaces
-            if shape == 1 {
-                poly1 = &polygon2;
-                poly2 = &self;
-            }
-
-            for i in 0..poly1.sides {
-                // index of next point
-                let next_i = (i + 1) % poly1.sides;
-                // get axis projection for side
-                let axis_proj = Vector2 {
-                    x: -(poly1.points[next_i].y - poly1.points[i].y),
-                    y: poly1.points[next_i].x - poly1.points[i].x,
-                };
-                // find min and max 1D points for poly1
-                let mut min_poly1 = f32::INFINITY;
-                let mut max_poly1 = f32::NEG_INFINITY;
-                for p in 0..poly1.sides {
-                    let dot_product = poly1.points[p].dot(axis_proj);
-                    min_poly1 = min_poly1.min(dot_product);
-                    max_poly1 = max_poly1.max(dot_product);
-                }
-                // find min and max 1D points for poly2
-                let mut min_poly2 = f32::INFINITY;
-                let mut max_poly2 = f32::NEG_INFINITY;
-                for p in 0..poly2.sides {
-                    let dot_product = poly2.points[p].dot(axis_proj);
-                    min_poly2 = min_poly2.min(dot_product);
-                    max_poly2 = max_poly2.max(dot_product);
-                }
-                // check if poly min/max values dont overlap
-                if !(max_poly2 >= min_poly1 && max_poly1 >= min_poly2) {
-                    self.colliding = false;
-                    return false;
-                }
-                // calculate actual overlap along projected axis, and store the minimum
-                overlap = (max_poly1.min(max_poly2) - min_poly1.max(min_poly2)).min(overlap);
-            }
+            return None;
         }
 
-        // if we got here, the objects have collided, we will displace poly1
-        // by `overlap` along the vector between the two object centers
-        let displacement = Vector2 {
-            x: polygon2.position.x - self.position.x,
-            y: polygon2.position.y - self.position.y,
+        let Some(contact) = self.contact(polygon2) else {
+            self.colliding = false;
+            return None;
         };
 
-        // displace poly1 position
-        if displacement.x.abs() > displacement.y.abs() {
-            self.position.x -= overlap / displacement.x;
-        } else {
-            self.position.y -= overlap / displacement.y;
-        }
+        // displace poly1 out of poly2
+        self.position += contact.separation();
+        self.update_transform();
 
         self.colliding = true;
-        return true;
+        Some(contact)
     }
 }
 
 impl Polygon2D {
     pub fn draw(&self, color: Color, raylib: &mut impl RaylibDraw) {
+        // convex decomposition
+        for part in &self.parts {
+            for i in 0..part.sides {
+                let i2 = (i + 1) % part.sides;
+                raylib.draw_line_v(part.points[i], part.points[i2], color.fade(0.3));
+            }
+        }
+
         // edges
         for i in 0..self.sides {
             let i2 = (i + 1) % self.sides;
@@ -264,3 +265,168 @@ impl Polygon2D {
         );
     }
 }
+
+impl CollisionObject for Polygon2D {
+    fn shape(&self) -> Shape2D<'_> {
+        Shape2D::Polygon(self)
+    }
+
+    fn one_way(&self) -> bool {
+        self.one_way
+    }
+
+    fn layer(&self) -> CollisionLayer {
+        self.layer
+    }
+
+    fn mask(&self) -> CollisionLayer {
+        self.mask
+    }
+
+    fn surface_velocity(&self) -> Vector2 {
+        self.velocity
+    }
+
+    fn bounds(&self) -> Rectangle {
+        // min & max of transformed points
+        let mut min = Vector2::new(f32::INFINITY, f32::INFINITY);
+        let mut max = Vector2::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+        for point in &self.points {
+            min.x = min.x.min(point.x);
+            min.y = min.y.min(point.y);
+            max.x = max.x.max(point.x);
+            max.y = max.y.max(point.y);
+        }
+        Rectangle::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+}
+
+/// 2D cross product of the edges (b - a) and (c - b)
+fn turn(a: Vector2, b: Vector2, c: Vector2) -> f32 {
+    (b.x - a.x) * (c.y - b.y) - (b.y - a.y) * (c.x - b.x)
+}
+
+/// Every corner turns the same way (collinear corners are ignored)
+fn is_convex(points: &[Vector2]) -> bool {
+    let count = points.len();
+    let mut sign = 0.0;
+    for i in 0..count {
+        let t = turn(points[i], points[(i + 1) % count], points[(i + 2) % count]);
+        if t.abs() <= f32::EPSILON {
+            continue;
+        }
+        if sign * t < 0.0 {
+            return false;
+        }
+        sign = t.signum();
+    }
+    true
+}
+
+/// Twice the signed area, positive for counter clockwise in y up coordinates
+fn signed_area(points: &[Vector2]) -> f32 {
+    (0..points.len())
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            a.x * b.y - a.y * b.x
+        })
+        .sum()
+}
+
+/// `point` inside or on the edge of the positively wound triangle
+fn in_triangle(point: Vector2, a: Vector2, b: Vector2, c: Vector2) -> bool {
+    turn(a, b, point) >= 0.0 && turn(b, c, point) >= 0.0 && turn(c, a, point) >= 0.0
+}
+
+/// Split a simple polygon into convex parts, as indices into `points`
+/// (ear clipping into triangles, then Hertel-Mehlhorn merging)
+fn convex_decomposition(points: &[Vector2]) -> Vec<Vec<usize>> {
+    if points.len() < 4 || is_convex(points) {
+        return vec![(0..points.len()).collect()];
+    }
+
+    // wind positively so ears turn the same way
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    if signed_area(points) < 0.0 {
+        remaining.reverse();
+    }
+
+    let mut triangles = Vec::new();
+    while remaining.len() > 3 {
+        let count = remaining.len();
+        let corner = |i: usize| {
+            (
+                remaining[(i + count - 1) % count],
+                remaining[i],
+                remaining[(i + 1) % count],
+            )
+        };
+
+        // collinear corners add nothing
+        if let Some(i) = (0..count).find(|&i| {
+            let (prev, current, next) = corner(i);
+            turn(points[prev], points[current], points[next]).abs() <= f32::EPSILON
+        }) {
+            remaining.remove(i);
+            continue;
+        }
+
+        // convex corner with no other vertex inside its triangle
+        let ear = (0..count).find(|&i| {
+            let (prev, current, next) = corner(i);
+            let (a, b, c) = (points[prev], points[current], points[next]);
+            turn(a, b, c) > 0.0
+                && remaining.iter().all(|&j| {
+                    [prev, current, next].contains(&j)
+                        || [a, b, c].contains(&points[j])
+                        || !in_triangle(points[j], a, b, c)
+                })
+        });
+
+        // self intersecting outline has no ears, clip anyway
+        let i = ear.unwrap_or(0);
+        let (prev, current, next) = corner(i);
+        triangles.push(vec![prev, current, next]);
+        remaining.remove(i);
+    }
+    triangles.push(remaining);
+
+    merge_convex(points, triangles)
+}
+
+/// Remove diagonals between parts while the merged part stays convex
+fn merge_convex(points: &[Vector2], mut parts: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    'search: loop {
+        for i in 0..parts.len() {
+            for j in i + 1..parts.len() {
+                let Some(merged) = merge_parts(&parts[i], &parts[j]) else {
+                    continue;
+                };
+                let merged_points: Vec<Vector2> = merged.iter().map(|&k| points[k]).collect();
+                if is_convex(&merged_points) {
+                    parts[i] = merged;
+                    parts.swap_remove(j);
+                    continue 'search;
+                }
+            }
+        }
+        return parts;
+    }
+}
+
+/// Join two equally wound parts along their shared edge, if they have one
+fn merge_parts(a: &[usize], b: &[usize]) -> Option<Vec<usize>> {
+    // edge (from, to) in a is (to, from) in b
+    for i in 0..a.len() {
+        let (from, to) = (a[i], a[(i + 1) % a.len()]);
+        let Some(j) = (0..b.len()).find(|&j| b[j] == to && b[(j + 1) % b.len()] == from) else {
+            continue;
+        };
+
+        // walk a from `to` around to `from`, then b past `from` back to `to`
+        let mut merged: Vec<usize> = (1..=a.len()).map(|k| a[(i + k) % a.len()]).collect();
+        merged.extend((2..b.len()).map(|k| b[(j + k) % b.len()]));
+        return Some(merged);
+    }
+    None
+}
@@ -0,0 +1,198 @@
+use super::{narrow_phase, CollisionObject, Contact2D, SpatialHash};
+use raylib::prelude::{Color, RaylibDraw, Rectangle, Vector2};
+use std::hash::Hash;
+
+/// Bisection passes used to refine a shape cast's time of impact
+const CAST_ITERATIONS: usize = 12;
+
+/// Where a moving shape first touches a collider
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeHit<Id> {
+    /// Collider that was hit
+    pub id: Id,
+    /// Contact at the time of impact, normal faces the moving shape
+    pub contact: Contact2D,
+    /// Fraction of the motion that can be moved without overlapping [0, 1]
+    pub fraction: f32,
+}
+
+impl<Id> ShapeHit<Id> {
+    pub fn point(&self) -> Vector2 {
+        self.contact.point()
+    }
+
+    pub fn normal(&self) -> Vector2 {
+        self.contact.normal
+    }
+
+    /// Draw hit point & normal
+    pub fn draw(&self, color: Color, raylib: &mut impl RaylibDraw) {
+        let point = self.point();
+        raylib.draw_circle_v(point, 5.0, color);
+        raylib.draw_line_v(point, point + self.normal() * 25.0, color);
+    }
+}
+
+/// Colliders `shape` overlaps right now, with their contacts
+/// (only colliders on a layer in the shape's mask, touching doesn't count)
+pub fn overlap<'a, Id>(
+    shape: &dyn CollisionObject,
+    colliders: impl IntoIterator<Item = (Id, &'a dyn CollisionObject)>,
+) -> Vec<(Id, Contact2D)> {
+    colliders
+        .into_iter()
+        .filter_map(|(id, other)| shape.contact(other).map(|contact| (id, contact)))
+        .filter(|(_, contact)| contact.depth > 0.0)
+        .collect()
+}
+
+/// `shape` overlaps any of `colliders` right now (same rules as `overlap`)
+pub fn overlaps_any<'a>(
+    shape: &dyn CollisionObject,
+    colliders: impl IntoIterator<Item = &'a dyn CollisionObject>,
+) -> bool {
+    colliders.into_iter().any(|other| {
+        shape
+            .contact(other)
+            .is_some_and(|contact| contact.depth > 0.0)
+    })
+}
+
+/// First collider `shape` hits while moving by `motion`, meant for area shapes (see `Ray2D::cast`)
+/// (one way colliders are only hit from above, never while already overlapping)
+pub fn shape_cast<'a, Id>(
+    shape: &dyn CollisionObject,
+    motion: Vector2,
+    colliders: impl IntoIterator<Item = (Id, &'a dyn CollisionObject)>,
+) -> Option<ShapeHit<Id>> {
+    colliders
+        .into_iter()
+        .filter_map(|(id, other)| cast(shape, motion, id, other))
+        .min_by(|a, b| a.fraction.total_cmp(&b.fraction))
+}
+
+/// Every collider `shape` hits while moving by `motion`, nearest first
+pub fn shape_cast_all<'a, Id>(
+    shape: &dyn CollisionObject,
+    motion: Vector2,
+    colliders: impl IntoIterator<Item = (Id, &'a dyn CollisionObject)>,
+) -> Vec<ShapeHit<Id>> {
+    let mut hits: Vec<ShapeHit<Id>> = colliders
+        .into_iter()
+        .filter_map(|(id, other)| cast(shape, motion, id, other))
+        .collect();
+    hits.sort_by(|a, b| a.fraction.total_cmp(&b.fraction));
+    hits
+}
+
+/// Bounding box covering `bounds` along all of `motion`
+pub fn swept_bounds(bounds: Rectangle, motion: Vector2) -> Rectangle {
+    Rectangle::new(
+        bounds.x + motion.x.min(0.0),
+        bounds.y + motion.y.min(0.0),
+        bounds.width + motion.x.abs(),
+        bounds.height + motion.y.abs(),
+    )
+}
+
+/// Step along the motion until overlapping, then bisect the time of impact
+fn cast<Id>(
+    shape: &dyn CollisionObject,
+    motion: Vector2,
+    id: Id,
+    other: &dyn CollisionObject,
+) -> Option<ShapeHit<Id>> {
+    let bounds = shape.bounds();
+    if !shape.detects(other)
+        || !narrow_phase::overlaps(&swept_bounds(bounds, motion), &other.bounds())
+    {
+        return None;
+    }
+
+    // touching doesn't count, so shapes can slide along surfaces
+    let contact_at = |t: f32| {
+        narrow_phase::contact_translated(shape.shape(), motion * t, other.shape())
+            .filter(|contact| contact.depth > 0.0)
+    };
+
+    // already overlapping
+    if let Some(contact) = contact_at(0.0) {
+        if other.one_way() {
+            return None;
+        }
+        return Some(ShapeHit {
+            id,
+            contact,
+            fraction: 0.0,
+        });
+    }
+
+    // steps no longer than half the shape, so thin colliders aren't skipped
+    let extent = (bounds.width.min(bounds.height) / 2.0).max(1.0);
+    let steps = (motion.length() / extent).ceil().max(1.0) as usize;
+
+    let mut previous = 0.0;
+    for i in 1..=steps {
+        let t = i as f32 / steps as f32;
+        let Some(mut contact) = contact_at(t) else {
+            previous = t;
+            continue;
+        };
+
+        // narrow down to the last free time
+        let (mut free, mut hit) = (previous, t);
+        for _ in 0..CAST_ITERATIONS {
+            let middle = (free + hit) / 2.0;
+            match contact_at(middle) {
+                Some(middle_contact) => {
+                    hit = middle;
+                    contact = middle_contact;
+                }
+                None => free = middle,
+            }
+        }
+
+        if other.one_way() && contact.normal.y >= 0.0 {
+            return None;
+        }
+        return Some(ShapeHit {
+            id,
+            contact,
+            fraction: free,
+        });
+    }
+
+    None
+}
+
+impl<Id: Copy + Eq + Hash> SpatialHash<Id> {
+    /// Stored shapes `shape` overlaps, `lookup` maps ids to their colliders
+    pub fn overlap<'a>(
+        &self,
+        shape: &dyn CollisionObject,
+        lookup: impl Fn(Id) -> &'a dyn CollisionObject,
+    ) -> Vec<(Id, Contact2D)> {
+        overlap(
+            shape,
+            self.query_rect(shape.bounds(), shape.mask())
+                .into_iter()
+                .map(|id| (id, lookup(id))),
+        )
+    }
+
+    /// First stored shape `shape` hits while moving by `motion`
+    pub fn shape_cast<'a>(
+        &self,
+        shape: &dyn CollisionObject,
+        motion: Vector2,
+        lookup: impl Fn(Id) -> &'a dyn CollisionObject,
+    ) -> Option<ShapeHit<Id>> {
+        shape_cast(
+            shape,
+            motion,
+            self.query_rect(swept_bounds(shape.bounds(), motion), shape.mask())
+                .into_iter()
+                .map(|id| (id, lookup(id))),
+        )
+    }
+}
@@ -1,9 +1,11 @@
-use super::Rect2D;
-use raylib::prelude::{Color, RaylibDraw, Vector2};
+use super::{CollisionLayer, CollisionObject, Rect2D, Shape2D};
+use raylib::prelude::{Color, RaylibDraw, Rectangle, Vector2};
 
 pub struct Ray2D {
     pub position: Vector2,
     pub direction: Vector2,
+    pub layer: CollisionLayer,
+    pub mask: CollisionLayer,
     contact_point: Vector2,
     contact_normal: Vector2,
     contact_time: f32,
@@ -24,6 +26,8 @@ impl Ray2D {
             contact_normal: Vector2::new(0.0, 0.0),
             contact_time: 0.0,
             colliding: 0,
+            layer: CollisionLayer::DEFAULT,
+            mask: CollisionLayer::ALL,
         }
     }
 
@@ -38,6 +42,16 @@ impl Ray2D {
         self
     }
 
+    pub const fn with_layer(mut self, layer: CollisionLayer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    pub const fn with_mask(mut self, mask: CollisionLayer) -> Self {
+        self.mask = mask;
+        self
+    }
+
     pub fn set_position(&mut self, x: f32, y: f32) {
         self.position.x = x;
         self.position.y = y;
@@ -55,6 +69,26 @@ impl Ray2D {
         self.colliding = 0;
     }
 
+    /// Hit point of the last successful check
+    pub const fn contact_point(&self) -> Vector2 {
+        self.contact_point
+    }
+
+    /// Surface normal of the last successful check
+    pub const fn contact_normal(&self) -> Vector2 {
+        self.contact_normal
+    }
+
+    /// Fraction of the ray length to the last successful check's hit
+    pub const fn contact_time(&self) -> f32 {
+        self.contact_time
+    }
+
+    /// Contact time & normal of the last rect check
+    pub(super) const fn contact(&self) -> (f32, Vector2) {
+        (self.contact_time, self.contact_normal)
+    }
+
     /// Draw ray line
     pub fn draw(&self, color: Color, raylib: &mut impl RaylibDraw) {
         // line
@@ -70,6 +104,10 @@ impl Ray2D {
 
 impl Ray2D {
     pub fn check_rect(&mut self, rect: &mut Rect2D) -> bool {
+        if !self.mask.intersects(rect.layer) {
+            return false;
+        }
+
         // Calculate intersections with rectangle bounding axes
         let mut t_near = (rect.position - self.position) / self.direction;
         let mut t_far = (rect.position + rect.size - self.position) / self.direction;
@@ -131,3 +169,49 @@ impl Ray2D {
         collide
     }
 }
+
+impl Ray2D {
+    /// Ray vs any collision shape, keeps the contact info of the hit
+    pub fn check(&mut self, other: &dyn CollisionObject) -> bool {
+        let Some(contact) = CollisionObject::contact(self, other) else {
+            return false;
+        };
+
+        // depth of ray contacts is the distance to the hit
+        let length = self.direction.length();
+        self.contact_time = if length > 0.0 {
+            contact.depth / length
+        } else {
+            0.0
+        };
+        self.contact_point = contact.point();
+        self.contact_normal = contact.normal;
+
+        self.colliding = self.colliding.saturating_add(1);
+        true
+    }
+}
+
+impl CollisionObject for Ray2D {
+    fn shape(&self) -> Shape2D<'_> {
+        Shape2D::Ray(self)
+    }
+
+    fn layer(&self) -> CollisionLayer {
+        self.layer
+    }
+
+    fn mask(&self) -> CollisionLayer {
+        self.mask
+    }
+
+    fn bounds(&self) -> Rectangle {
+        let end = self.position + self.direction;
+        Rectangle::new(
+            self.position.x.min(end.x),
+            self.position.y.min(end.y),
+            self.direction.x.abs(),
+            self.direction.y.abs(),
+        )
+    }
+}
@@ -0,0 +1,114 @@
+use super::{CollisionObject, Ray2D, SpatialHash};
+use raylib::prelude::{Color, RaylibDraw, Vector2};
+use std::hash::Hash;
+
+/// Where a ray first touches a collider
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit<Id> {
+    /// Collider that was hit
+    pub id: Id,
+    pub point: Vector2,
+    /// Surface normal at the hit, faces the ray position
+    pub normal: Vector2,
+    /// Distance from the ray position to the hit
+    pub distance: f32,
+    /// Distance as a fraction of the ray length [0, 1]
+    pub fraction: f32,
+}
+
+impl<Id> RayHit<Id> {
+    /// Draw hit point & normal
+    pub fn draw(&self, color: Color, raylib: &mut impl RaylibDraw) {
+        raylib.draw_circle_v(self.point, 5.0, color);
+        raylib.draw_line_v(self.point, self.point + self.normal * 25.0, color);
+    }
+}
+
+impl Ray2D {
+    /// Ray segment from `start` to `end`
+    pub fn between(start: Vector2, end: Vector2) -> Self {
+        Self::new()
+            .with_position(start.x, start.y)
+            .with_direction(end - start)
+    }
+
+    /// End of the ray segment
+    pub fn end(&self) -> Vector2 {
+        self.position + self.direction
+    }
+
+    /// Hit against one collider, without changing the ray's contact state
+    /// (one way colliders are only hit from above, rays starting inside hit at distance 0)
+    pub fn hit<Id>(&self, id: Id, other: &dyn CollisionObject) -> Option<RayHit<Id>> {
+        let contact = CollisionObject::contact(self, other)?;
+
+        if other.one_way() && contact.normal.y >= 0.0 {
+            return None;
+        }
+
+        let length = self.direction.length();
+        Some(RayHit {
+            id,
+            point: contact.point(),
+            normal: contact.normal,
+            distance: contact.depth,
+            fraction: if length > 0.0 {
+                contact.depth / length
+            } else {
+                0.0
+            },
+        })
+    }
+
+    /// Nearest hit among `colliders` on a layer in the ray's mask
+    pub fn cast<'a, Id>(
+        &self,
+        colliders: impl IntoIterator<Item = (Id, &'a dyn CollisionObject)>,
+    ) -> Option<RayHit<Id>> {
+        colliders
+            .into_iter()
+            .filter_map(|(id, other)| self.hit(id, other))
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+    }
+
+    /// Every hit among `colliders` on a layer in the ray's mask, nearest first
+    pub fn cast_all<'a, Id>(
+        &self,
+        colliders: impl IntoIterator<Item = (Id, &'a dyn CollisionObject)>,
+    ) -> Vec<RayHit<Id>> {
+        let mut hits: Vec<RayHit<Id>> = colliders
+            .into_iter()
+            .filter_map(|(id, other)| self.hit(id, other))
+            .collect();
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+}
+
+impl<Id: Copy + Eq + Hash> SpatialHash<Id> {
+    /// Nearest hit of `ray` against the stored shapes, `lookup` maps ids to their colliders
+    pub fn raycast<'a>(
+        &self,
+        ray: &Ray2D,
+        lookup: impl Fn(Id) -> &'a dyn CollisionObject,
+    ) -> Option<RayHit<Id>> {
+        ray.cast(
+            self.query_rect(ray.bounds(), ray.mask)
+                .into_iter()
+                .map(|id| (id, lookup(id))),
+        )
+    }
+
+    /// Every hit of `ray` against the stored shapes, nearest first
+    pub fn raycast_all<'a>(
+        &self,
+        ray: &Ray2D,
+        lookup: impl Fn(Id) -> &'a dyn CollisionObject,
+    ) -> Vec<RayHit<Id>> {
+        ray.cast_all(
+            self.query_rect(ray.bounds(), ray.mask)
+                .into_iter()
+                .map(|id| (id, lookup(id))),
+        )
+    }
+}
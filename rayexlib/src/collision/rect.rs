@@ -1,19 +1,20 @@
-use super::ColliderInfo;
+use super::{ColliderInfo, CollisionLayer, CollisionObject, Contact2D, Ray2D, Shape2D};
 use raylib::prelude::{Color, RaylibDraw, Rectangle, Vector2};
 
-#[derive(PartialEq)]
-pub enum CollisionDirection {
-    Right,
-    Left,
-    Up,
-    Down,
-}
+/// Minimum normal component for a contact to count as a floor, roof or wall (45°)
+const SIDE_THRESHOLD: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// Deepest a falling rect may sink into a one way rect and still land on it
+const ONE_WAY_MARGIN: f32 = 16.0;
 
 pub struct Rect2D {
     pub position: Vector2,
     pub size: Vector2,
     pub velocity: Vector2,
-    pub colliding: Vec<CollisionDirection>,
+    pub contacts: Vec<Contact2D>,
+    pub one_way: bool,
+    pub layer: CollisionLayer,
+    pub mask: CollisionLayer,
 }
 
 impl Rect2D {
@@ -22,7 +23,10 @@ impl Rect2D {
             position: Vector2::new(0.0, 0.0),
             size: Vector2::new(width, height),
             velocity: Vector2::new(0.0, 0.0),
-            colliding: Vec::new(),
+            contacts: Vec::new(),
+            one_way: false,
+            layer: CollisionLayer::DEFAULT,
+            mask: CollisionLayer::ALL,
         }
     }
 
@@ -31,7 +35,10 @@ impl Rect2D {
             position: Vector2::new(0.0, 0.0),
             size,
             velocity: Vector2::new(0.0, 0.0),
-            colliding: Vec::new(),
+            contacts: Vec::new(),
+            one_way: false,
+            layer: CollisionLayer::DEFAULT,
+            mask: CollisionLayer::ALL,
         }
     }
 
@@ -47,53 +54,76 @@ impl Rect2D {
         self
     }
 
+    /// Make rect solid only from above
+    pub const fn with_one_way(mut self) -> Self {
+        self.one_way = true;
+        self
+    }
+
+    pub const fn with_layer(mut self, layer: CollisionLayer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    pub const fn with_mask(mut self, mask: CollisionLayer) -> Self {
+        self.mask = mask;
+        self
+    }
+
     pub fn on_floor(&self) -> bool {
-        self.colliding.contains(&CollisionDirection::Down)
+        self.contacts
+            .iter()
+            .any(|contact| contact.normal.y <= -SIDE_THRESHOLD)
     }
 
     pub fn on_roof(&self) -> bool {
-        self.colliding.contains(&CollisionDirection::Up)
+        self.contacts
+            .iter()
+            .any(|contact| contact.normal.y >= SIDE_THRESHOLD)
     }
 
     pub fn on_wall(&self) -> bool {
-        self.colliding
-            .iter()
-            .any(|x| x == &CollisionDirection::Left || x == &CollisionDirection::Right)
+        self.on_wall_left() || self.on_wall_right()
     }
 
     pub fn on_wall_left(&self) -> bool {
-        self.colliding.contains(&CollisionDirection::Left)
+        self.contacts
+            .iter()
+            .any(|contact| contact.normal.x >= SIDE_THRESHOLD)
     }
 
     pub fn on_wall_right(&self) -> bool {
-        self.colliding.contains(&CollisionDirection::Right)
+        self.contacts
+            .iter()
+            .any(|contact| contact.normal.x <= -SIDE_THRESHOLD)
     }
 
+    /// Direction towards touched sides
     pub fn direction(&self) -> Vector2 {
         let mut v = Vector2::new(0.0, 0.0);
-        for x in &self.colliding {
-            match x {
-                CollisionDirection::Up => v.y -= 1.0,
-                CollisionDirection::Down => v.y += 1.0,
-                CollisionDirection::Left => v.x -= 1.0,
-                CollisionDirection::Right => v.x += 1.0,
-            }
+        for contact in &self.contacts {
+            v -= contact.normal;
         }
         v.normalized()
     }
 
     pub fn reset_colliding(&mut self) {
-        self.colliding.clear()
+        self.contacts.clear()
     }
 }
 
 impl Rect2D {
     pub fn draw(&self, color: Color, raylib: &mut impl RaylibDraw) {
+        self.draw_at(self.position, color, raylib);
+    }
+
+    /// Draw as if placed at `position`
+    pub fn draw_at(&self, position: Vector2, color: Color, raylib: &mut impl RaylibDraw) {
         // outline
         raylib.draw_rectangle_lines_ex(
             Rectangle {
-                x: self.position.x,
-                y: self.position.y,
+                x: position.x,
+                y: position.y,
                 width: self.size.x,
                 height: self.size.y,
             },
@@ -103,7 +133,7 @@ impl Rect2D {
 
         // center
         raylib.draw_circle_v(
-            self.position + self.size / 2.0,
+            position + self.size / 2.0,
             (self.size.x * self.size.y).sqrt() / 10.0,
             color.fade(0.5),
         );
@@ -121,78 +151,130 @@ impl Rect2D {
 
     /// Rect vs Rect collision check (aabb)
     pub fn check_rect(&self, rect2: &Rect2D) -> bool {
-        self.position.x >= (rect2.position.x - self.size.x)
+        self.detects(rect2)
+            && self.position.x >= (rect2.position.x - self.size.x)
             && self.position.x <= (rect2.position.x + rect2.size.x)
             && self.position.y >= (rect2.position.y - self.size.y)
             && self.position.y <= (rect2.position.y + rect2.size.y)
     }
 
     /// Rect vs Rect collision resolution (aabb dynamic)
-    pub fn collide_rect(&mut self, rect2: &mut Rect2D) -> bool {
-        if self.check_rect(rect2) {
-            // get center of rectangles
-            let self_center = self.position + (self.size / 2.0);
-            let other_center = rect2.position + (rect2.size / 2.0);
-
-            // get rect sides
-            let self_right = self.position.x + self.size.x;
-            let self_bottom = self.position.y + self.size.y;
-            let other_right = rect2.position.x + rect2.size.x;
-            let other_bottom = rect2.position.y + rect2.size.y;
-
-            // calc displacement of centers
-            let displacement = Vector2::new(
-                self_center.x - other_center.x,
-                self_center.y - other_center.y,
-            );
-
-            // check which side to resolve on, both axis
-            let horizontal = displacement.x < 0.0;
-            let vertical = displacement.y < 0.0;
-
-            // get x axis offset
-            let x_offset = if horizontal {
-                self_right - rect2.position.x // left side
+    pub fn collide_rect(&mut self, rect2: &mut Rect2D) -> Option<Contact2D> {
+        let contact = self.contact(rect2)?;
+
+        // one way rects only catch rects falling onto their top
+        if rect2.one_way
+            && (contact.normal.y >= 0.0
+                || self.velocity.y < rect2.velocity.y
+                || contact.depth > ONE_WAY_MARGIN)
+        {
+            return None;
+        }
+
+        // resolve rect1 position, snapped to the contact side
+        if contact.normal.x != 0.0 {
+            self.position.x = if contact.normal.x < 0.0 {
+                rect2.position.x - self.size.x // move left
             } else {
-                other_right - self.position.x // right side
+                rect2.position.x + rect2.size.x // move right
             };
-
-            // get y axis offset
-            let y_offset = if vertical {
-                self_bottom - rect2.position.y // top side
+        } else {
+            self.position.y = if contact.normal.y < 0.0 {
+                rect2.position.y - self.size.y // move up
             } else {
-                other_bottom - self.position.y // bottom side
+                rect2.position.y + rect2.size.y // move down
             };
+        }
 
-            // resolve rect1 position
-            if x_offset < y_offset {
-                if horizontal {
-                    self.position.x = rect2.position.x - self.size.x; // move left
-                    self.colliding.push(CollisionDirection::Right);
-                    rect2.colliding.push(CollisionDirection::Left);
-                } else {
-                    self.position.x = other_right; // move right
-                    self.colliding.push(CollisionDirection::Left);
-                    rect2.colliding.push(CollisionDirection::Right);
-                }
-            } else {
-                if vertical {
-                    self.position.y = rect2.position.y - self.size.y; // move up
-                    self.colliding.push(CollisionDirection::Down);
-                    rect2.colliding.push(CollisionDirection::Up);
-                } else {
-                    self.position.y = other_bottom; // move down
-                    self.colliding.push(CollisionDirection::Up);
-                    rect2.colliding.push(CollisionDirection::Down);
-                }
+        // store contacts on both rects
+        rect2.contacts.push(contact.clone().flipped());
+        self.contacts.push(contact.clone());
+
+        Some(contact)
+    }
+
+    /// Rect vs Rect swept collision check (continuous aabb)
+    /// Returns time of impact [0, 1] & contact normal of this frame's motion
+    pub fn sweep(&self, rect2: &Rect2D, frame_time: f32) -> Option<(f32, Vector2)> {
+        if !self.detects(rect2) {
+            return None;
+        }
+
+        // relative motion for this frame
+        let motion = (self.velocity - rect2.velocity) * frame_time;
+        if motion.x == 0.0 && motion.y == 0.0 {
+            return None;
+        }
+
+        // expand target by self size, so self can be treated as a point
+        let mut expanded = Rect2D::newv(rect2.size + self.size).with_position(
+            rect2.position.x - (self.size.x / 2.0),
+            rect2.position.y - (self.size.y / 2.0),
+        );
+
+        // cast ray from self center along motion
+        let center = self.position + (self.size / 2.0);
+
+        // no motion along an axis divides by zero in the ray's slab test,
+        // only a center strictly within that slab can hit
+        let within = |center: f32, start: f32, size: f32| center > start && center < start + size;
+        if (motion.x == 0.0 && !within(center.x, expanded.position.x, expanded.size.x))
+            || (motion.y == 0.0 && !within(center.y, expanded.position.y, expanded.size.y))
+        {
+            return None;
+        }
+
+        let mut ray = Ray2D::new()
+            .with_position(center.x, center.y)
+            .with_direction(motion);
+
+        if ray.check_rect(&mut expanded) {
+            let (time, normal) = ray.contact();
+
+            // diagonal contacts have no normal, nothing to resolve
+            if normal != Vector2::zero() && time.is_finite() {
+                return Some((time, normal));
             }
+        }
 
-            // return colliding
-            true
-        } else {
-            // return colliding
-            false
+        None
+    }
+
+    /// Rect vs Rect swept collision resolution (continuous aabb)
+    /// Moves self onto the contact side & removes velocity into rect2,
+    /// should be called before velocity is applied to position
+    pub fn collide_rect_swept(&mut self, rect2: &Rect2D, frame_time: f32) -> Option<Contact2D> {
+        let (time, normal) = self.sweep(rect2, frame_time)?;
+
+        // one way rects only catch rects falling onto their top
+        if rect2.one_way && normal.y >= 0.0 {
+            return None;
         }
+
+        // center of self at time of impact
+        let impact = self.center() + (self.velocity - rect2.velocity) * frame_time * time;
+
+        let point = if normal.x != 0.0 {
+            // snap to contact side, stop horizontal motion
+            self.position.x = if normal.x < 0.0 {
+                rect2.position.x - self.size.x // left side
+            } else {
+                rect2.position.x + rect2.size.x // right side
+            };
+            self.velocity.x = rect2.velocity.x;
+            Vector2::new(impact.x - normal.x * (self.size.x / 2.0), impact.y)
+        } else {
+            // snap to contact side, stop vertical motion
+            self.position.y = if normal.y < 0.0 {
+                rect2.position.y - self.size.y // top side
+            } else {
+                rect2.position.y + rect2.size.y // bottom side
+            };
+            self.velocity.y = rect2.velocity.y;
+            Vector2::new(impact.x, impact.y - normal.y * (self.size.y / 2.0))
+        };
+
+        Some(Contact2D::new(normal, 0.0, vec![point]))
     }
 
     /// Rect vs Vector2
@@ -213,3 +295,29 @@ impl ColliderInfo for Rect2D {
         self.velocity
     }
 }
+
+impl CollisionObject for Rect2D {
+    fn shape(&self) -> Shape2D<'_> {
+        Shape2D::Rect(self)
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(self.position.x, self.position.y, self.size.x, self.size.y)
+    }
+
+    fn one_way(&self) -> bool {
+        self.one_way
+    }
+
+    fn layer(&self) -> CollisionLayer {
+        self.layer
+    }
+
+    fn mask(&self) -> CollisionLayer {
+        self.mask
+    }
+
+    fn surface_velocity(&self) -> Vector2 {
+        self.velocity
+    }
+}
@@ -0,0 +1,477 @@
+use super::{Circle2D, CollisionLayer, CollisionObject, Contact2D, Polygon2D, Rect2D, Shape2D};
+use raylib::prelude::{Color, RaylibDraw, Rectangle, Vector2};
+
+/// Collider owned by a rigid body
+pub enum BodyShape2D {
+    Circle(Circle2D),
+    /// Axis aligned, never rotates
+    Rect(Rect2D),
+    /// Rotates around its position, model points should be centered on it
+    Polygon(Polygon2D),
+}
+
+/// Dynamic body moved by forces & impulses, bounces off other bodies & colliders
+pub struct RigidBody2D {
+    pub shape: BodyShape2D,
+    pub velocity: Vector2,
+    /// Radians per second
+    pub angular_velocity: f32,
+    /// Radians, applied to polygons
+    pub rotation: f32,
+    /// Bounciness [0, 1]
+    pub restitution: f32,
+    /// Coulomb friction coefficient
+    pub friction: f32,
+    pub gravity_scale: f32,
+    pub linear_damping: f32,
+    pub angular_damping: f32,
+    inverse_mass: f32,
+    inverse_inertia: f32,
+    force: Vector2,
+    torque: f32,
+}
+
+impl RigidBody2D {
+    pub const SOLVER_ITERATIONS: usize = 4;
+    /// Fraction of the remaining overlap removed each step
+    pub const CORRECTION_PERCENT: f32 = 0.6;
+    /// Overlap allowed before positions are corrected (pixels)
+    pub const CORRECTION_SLOP: f32 = 0.5;
+
+    /// Dynamic body, `mass` of 0 makes it static
+    pub fn new(shape: BodyShape2D, mass: f32) -> Self {
+        let mut body = Self {
+            shape,
+            velocity: Vector2::zero(),
+            angular_velocity: 0.0,
+            rotation: 0.0,
+            restitution: 0.2,
+            friction: 0.4,
+            gravity_scale: 1.0,
+            linear_damping: 0.0,
+            angular_damping: 0.0,
+            inverse_mass: 0.0,
+            inverse_inertia: 0.0,
+            force: Vector2::zero(),
+            torque: 0.0,
+        };
+        body.set_mass(mass);
+        body
+    }
+
+    /// Immovable body, only pushes other bodies
+    pub fn new_static(shape: BodyShape2D) -> Self {
+        Self::new(shape, 0.0)
+    }
+
+    pub const fn with_restitution(mut self, restitution: f32) -> Self {
+        self.restitution = restitution;
+        self
+    }
+
+    pub const fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    pub const fn with_gravity_scale(mut self, gravity_scale: f32) -> Self {
+        self.gravity_scale = gravity_scale;
+        self
+    }
+
+    pub const fn with_damping(mut self, linear: f32, angular: f32) -> Self {
+        self.linear_damping = linear;
+        self.angular_damping = angular;
+        self
+    }
+
+    pub const fn with_velocity(mut self, velocity: Vector2) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    pub fn mass(&self) -> f32 {
+        if self.inverse_mass > 0.0 {
+            1.0 / self.inverse_mass
+        } else {
+            0.0
+        }
+    }
+
+    pub const fn inverse_mass(&self) -> f32 {
+        self.inverse_mass
+    }
+
+    pub const fn inverse_inertia(&self) -> f32 {
+        self.inverse_inertia
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.inverse_mass == 0.0
+    }
+
+    /// Set mass & the matching moment of inertia of the shape
+    pub fn set_mass(&mut self, mass: f32) {
+        if mass <= 0.0 {
+            self.inverse_mass = 0.0;
+            self.inverse_inertia = 0.0;
+            return;
+        }
+
+        let inertia = match &self.shape {
+            BodyShape2D::Circle(circle) => 0.5 * mass * circle.radius * circle.radius,
+            BodyShape2D::Rect(_) => 0.0,
+            BodyShape2D::Polygon(polygon) => polygon_inertia(polygon.model_points(), mass),
+        };
+
+        self.inverse_mass = 1.0 / mass;
+        self.inverse_inertia = if inertia > 0.0 { 1.0 / inertia } else { 0.0 };
+    }
+
+    /// Center of mass in world space
+    pub fn center(&self) -> Vector2 {
+        match &self.shape {
+            BodyShape2D::Circle(circle) => circle.position,
+            BodyShape2D::Rect(rect) => rect.position + (rect.size / 2.0),
+            BodyShape2D::Polygon(polygon) => polygon.position,
+        }
+    }
+
+    /// Velocity of a world space point on the body
+    pub fn velocity_at(&self, point: Vector2) -> Vector2 {
+        let r = point - self.center();
+        self.velocity + Vector2::new(-r.y, r.x) * self.angular_velocity
+    }
+
+    /// Force applied over the next step
+    pub fn apply_force(&mut self, force: Vector2) {
+        self.force += force;
+    }
+
+    /// Torque applied over the next step
+    pub fn apply_torque(&mut self, torque: f32) {
+        self.torque += torque;
+    }
+
+    /// Instant change of momentum at a world space point
+    pub fn apply_impulse(&mut self, impulse: Vector2, point: Vector2) {
+        let r = point - self.center();
+        self.velocity += impulse * self.inverse_mass;
+        self.angular_velocity += cross(r, impulse) * self.inverse_inertia;
+    }
+
+    /// Instant change of momentum through the center of mass
+    pub fn apply_central_impulse(&mut self, impulse: Vector2) {
+        self.velocity += impulse * self.inverse_mass;
+    }
+
+    pub fn translate(&mut self, offset: Vector2) {
+        match &mut self.shape {
+            BodyShape2D::Circle(circle) => circle.position += offset,
+            BodyShape2D::Rect(rect) => rect.position += offset,
+            BodyShape2D::Polygon(polygon) => {
+                polygon.position += offset;
+                polygon.update_transform();
+            }
+        }
+    }
+
+    /// Apply gravity & forces, then move by velocity (semi-implicit euler)
+    pub fn integrate(&mut self, gravity: Vector2, frame_time: f32) {
+        if !self.is_static() {
+            self.velocity +=
+                (gravity * self.gravity_scale + self.force * self.inverse_mass) * frame_time;
+            self.angular_velocity += self.torque * self.inverse_inertia * frame_time;
+
+            // damping
+            self.velocity /= 1.0 + self.linear_damping * frame_time;
+            self.angular_velocity /= 1.0 + self.angular_damping * frame_time;
+
+            self.translate(self.velocity * frame_time);
+            if self.inverse_inertia > 0.0 {
+                self.rotation += self.angular_velocity * frame_time;
+            }
+        }
+
+        self.force = Vector2::zero();
+        self.torque = 0.0;
+        self.sync_shape();
+    }
+
+    /// Copy body motion onto the shape
+    fn sync_shape(&mut self) {
+        match &mut self.shape {
+            BodyShape2D::Circle(_) => {}
+            BodyShape2D::Rect(rect) => rect.velocity = self.velocity,
+            BodyShape2D::Polygon(polygon) => {
+                polygon.velocity = self.velocity;
+                polygon.rotation = self.rotation;
+                polygon.update_transform();
+            }
+        }
+    }
+}
+
+impl RigidBody2D {
+    /// Step all bodies, resolving contacts between them & against static `colliders`
+    pub fn simulate(
+        bodies: &mut [RigidBody2D],
+        colliders: &[&dyn CollisionObject],
+        gravity: Vector2,
+        frame_time: f32,
+    ) {
+        // split fast motion into steps so bodies can't pass through thin shapes
+        let steps = bodies
+            .iter()
+            .map(|body| {
+                let bounds = body.bounds();
+                let size = bounds.width.min(bounds.height).max(1.0);
+                ((body.velocity.length() * frame_time) / (size / 2.0)).ceil() as usize
+            })
+            .max()
+            .unwrap_or(1)
+            .clamp(1, 8);
+        let step_time = frame_time / steps as f32;
+
+        for _ in 0..steps {
+            for body in bodies.iter_mut() {
+                body.integrate(gravity, step_time);
+            }
+
+            // find contacts once per step
+            let mut pairs = Vec::new();
+            for i in 0..bodies.len() {
+                for j in (i + 1)..bodies.len() {
+                    if let Some(contact) = bodies[i].contact(&bodies[j]) {
+                        pairs.push((i, j, contact));
+                    }
+                }
+            }
+
+            let mut statics = Vec::new();
+            for (i, body) in bodies.iter().enumerate() {
+                for (c, other) in colliders.iter().enumerate() {
+                    if let Some(contact) = body.contact(*other) {
+                        statics.push((i, c, contact));
+                    }
+                }
+            }
+
+            // velocity response
+            for _ in 0..Self::SOLVER_ITERATIONS {
+                for (i, j, contact) in &pairs {
+                    let (a, b) = pair_mut(bodies, *i, *j);
+                    a.resolve_velocity(Some(b), Vector2::zero(), contact);
+                }
+                for (i, c, contact) in &statics {
+                    let surface = colliders[*c].surface_velocity();
+                    bodies[*i].resolve_velocity(None, surface, contact);
+                }
+            }
+
+            // push apart what is still overlapping
+            for (i, j, contact) in &pairs {
+                let (a, b) = pair_mut(bodies, *i, *j);
+                a.correct_position(Some(b), contact);
+            }
+            for (i, _, contact) in &statics {
+                bodies[*i].correct_position(None, contact);
+            }
+
+            for body in bodies.iter_mut() {
+                body.sync_shape();
+            }
+        }
+    }
+
+    /// Bounce & friction impulses of a contact
+    fn resolve_velocity(
+        &mut self,
+        mut other: Option<&mut RigidBody2D>,
+        surface: Vector2,
+        contact: &Contact2D,
+    ) {
+        let normal = contact.normal;
+        let points = if contact.points.is_empty() {
+            vec![self.center()]
+        } else {
+            contact.points.clone()
+        };
+
+        // material of the pair
+        let (restitution, friction) = match &other {
+            Some(other) => (
+                self.restitution.max(other.restitution),
+                (self.friction * other.friction).sqrt(),
+            ),
+            None => (self.restitution, self.friction),
+        };
+
+        for point in &points {
+            let other_velocity = other
+                .as_ref()
+                .map_or(surface, |other| other.velocity_at(*point));
+            let relative = self.velocity_at(*point) - other_velocity;
+            let along_normal = relative.dot(normal);
+
+            // already separating
+            if along_normal > 0.0 {
+                continue;
+            }
+
+            // normal impulse, shared between contact points
+            let mass = self.effective_mass(&other, *point, normal);
+            if mass <= 0.0 {
+                return;
+            }
+            let j = -(1.0 + restitution) * along_normal / mass / points.len() as f32;
+            self.exchange_impulse(&mut other, normal * j, *point);
+
+            // friction impulse along the surface, clamped by the normal impulse
+            let other_velocity = other
+                .as_ref()
+                .map_or(surface, |other| other.velocity_at(*point));
+            let relative = self.velocity_at(*point) - other_velocity;
+            let tangent = relative - normal * relative.dot(normal);
+            if tangent.length() <= f32::EPSILON {
+                continue;
+            }
+            let tangent = tangent.normalized();
+
+            let mass = self.effective_mass(&other, *point, tangent);
+            let jt = (-relative.dot(tangent) / mass / points.len() as f32)
+                .clamp(-j * friction, j * friction);
+            self.exchange_impulse(&mut other, tangent * jt, *point);
+        }
+    }
+
+    /// Move bodies out of each other by their share of inverse mass
+    fn correct_position(&mut self, other: Option<&mut RigidBody2D>, contact: &Contact2D) {
+        let other_inverse_mass = other.as_ref().map_or(0.0, |other| other.inverse_mass);
+        let total = self.inverse_mass + other_inverse_mass;
+        if total <= 0.0 {
+            return;
+        }
+
+        let depth = (contact.depth - Self::CORRECTION_SLOP).max(0.0);
+        let correction = contact.normal * (depth / total * Self::CORRECTION_PERCENT);
+
+        self.translate(correction * self.inverse_mass);
+        if let Some(other) = other {
+            other.translate(-(correction * other.inverse_mass));
+        }
+    }
+
+    /// Inverse of the mass felt by an impulse along `direction` at `point`
+    fn effective_mass(
+        &self,
+        other: &Option<&mut RigidBody2D>,
+        point: Vector2,
+        direction: Vector2,
+    ) -> f32 {
+        let ra = cross(point - self.center(), direction);
+        let mut mass = self.inverse_mass + ra * ra * self.inverse_inertia;
+
+        if let Some(other) = other {
+            let rb = cross(point - other.center(), direction);
+            mass += other.inverse_mass + rb * rb * other.inverse_inertia;
+        }
+        mass
+    }
+
+    fn exchange_impulse(
+        &mut self,
+        other: &mut Option<&mut RigidBody2D>,
+        impulse: Vector2,
+        point: Vector2,
+    ) {
+        self.apply_impulse(impulse, point);
+        if let Some(other) = other {
+            other.apply_impulse(-impulse, point);
+        }
+    }
+}
+
+impl RigidBody2D {
+    pub fn draw(&self, color: Color, raylib: &mut impl RaylibDraw) {
+        match &self.shape {
+            BodyShape2D::Circle(circle) => {
+                circle.draw(color, raylib);
+
+                // spoke to show rotation
+                let spoke = Vector2::new(self.rotation.cos(), self.rotation.sin()) * circle.radius;
+                raylib.draw_line_v(circle.position, circle.position + spoke, color);
+            }
+            BodyShape2D::Rect(rect) => rect.draw(color, raylib),
+            BodyShape2D::Polygon(polygon) => polygon.draw(color, raylib),
+        }
+    }
+}
+
+impl CollisionObject for RigidBody2D {
+    fn shape(&self) -> Shape2D<'_> {
+        match &self.shape {
+            BodyShape2D::Circle(circle) => circle.shape(),
+            BodyShape2D::Rect(rect) => rect.shape(),
+            BodyShape2D::Polygon(polygon) => polygon.shape(),
+        }
+    }
+
+    fn bounds(&self) -> Rectangle {
+        self.shape().bounds()
+    }
+
+    fn layer(&self) -> CollisionLayer {
+        match &self.shape {
+            BodyShape2D::Circle(circle) => circle.layer,
+            BodyShape2D::Rect(rect) => rect.layer,
+            BodyShape2D::Polygon(polygon) => polygon.layer,
+        }
+    }
+
+    fn mask(&self) -> CollisionLayer {
+        match &self.shape {
+            BodyShape2D::Circle(circle) => circle.mask,
+            BodyShape2D::Rect(rect) => rect.mask,
+            BodyShape2D::Polygon(polygon) => polygon.mask,
+        }
+    }
+
+    fn surface_velocity(&self) -> Vector2 {
+        self.velocity
+    }
+}
+
+/// 2D cross product (z of the 3D cross product)
+fn cross(a: Vector2, b: Vector2) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+/// Moment of inertia of a polygon around its local origin
+fn polygon_inertia(points: &[Vector2], mass: f32) -> f32 {
+    let mut area = 0.0;
+    let mut inertia = 0.0;
+    for i in 0..points.len() {
+        let a = points[i];
+        let b = points[(i + 1) % points.len()];
+        let c = cross(a, b);
+        area += c;
+        inertia += c * (a.dot(a) + a.dot(b) + b.dot(b));
+    }
+
+    if area.abs() <= f32::EPSILON {
+        return 0.0;
+    }
+    mass * inertia / (6.0 * area)
+}
+
+/// Two distinct bodies of a slice, borrowed mutably
+fn pair_mut(
+    bodies: &mut [RigidBody2D],
+    i: usize,
+    j: usize,
+) -> (&mut RigidBody2D, &mut RigidBody2D) {
+    debug_assert!(i < j);
+    let (left, right) = bodies.split_at_mut(j);
+    (&mut left[i], &mut right[0])
+}
@@ -0,0 +1,97 @@
+use super::{narrow_phase, CollisionLayer, CollisionObject, Shape2D};
+use raylib::prelude::{Color, RaylibDraw, Rectangle, Vector2};
+
+/// Solid line between two points, e.g. thin ledges & level outlines
+pub struct Segment2D {
+    pub start: Vector2,
+    pub end: Vector2,
+    pub one_way: bool,
+    pub layer: CollisionLayer,
+    pub mask: CollisionLayer,
+}
+
+impl Segment2D {
+    pub const fn new(start: Vector2, end: Vector2) -> Segment2D {
+        Segment2D {
+            start,
+            end,
+            one_way: false,
+            layer: CollisionLayer::DEFAULT,
+            mask: CollisionLayer::ALL,
+        }
+    }
+
+    /// Make segment solid only from above
+    pub const fn with_one_way(mut self) -> Self {
+        self.one_way = true;
+        self
+    }
+
+    pub const fn with_layer(mut self, layer: CollisionLayer) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    pub const fn with_mask(mut self, mask: CollisionLayer) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    pub fn length(&self) -> f32 {
+        (self.end - self.start).length()
+    }
+
+    /// Unit normal facing up (or right for vertical segments)
+    pub fn normal(&self) -> Vector2 {
+        let edge = self.end - self.start;
+        let normal = Vector2::new(edge.y, -edge.x).normalized();
+        if normal.y > 0.0 || (normal.y == 0.0 && normal.x < 0.0) {
+            -normal
+        } else {
+            normal
+        }
+    }
+
+    /// Closest point on the segment to `point`
+    pub fn closest_point(&self, point: Vector2) -> Vector2 {
+        narrow_phase::closest_on_segment(point, self.start, self.end)
+    }
+}
+
+impl Segment2D {
+    pub fn draw(&self, color: Color, raylib: &mut impl RaylibDraw) {
+        // line
+        raylib.draw_line_ex(self.start, self.end, 2.0, color);
+
+        // end points
+        raylib.draw_circle_v(self.start, 4.0, color.fade(0.5));
+        raylib.draw_circle_v(self.end, 4.0, color.fade(0.5));
+    }
+}
+
+impl CollisionObject for Segment2D {
+    fn shape(&self) -> Shape2D<'_> {
+        Shape2D::Segment(self)
+    }
+
+    fn one_way(&self) -> bool {
+        self.one_way
+    }
+
+    fn layer(&self) -> CollisionLayer {
+        self.layer
+    }
+
+    fn mask(&self) -> CollisionLayer {
+        self.mask
+    }
+
+    fn bounds(&self) -> Rectangle {
+        Rectangle::new(
+            self.start.x.min(self.end.x),
+            self.start.y.min(self.end.y),
+            (self.end.x - self.start.x).abs(),
+            (self.end.y - self.start.y).abs(),
+        )
+    }
+}
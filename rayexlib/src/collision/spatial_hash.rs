@@ -0,0 +1,225 @@
+use super::{narrow_phase::overlaps, CollisionLayer, CollisionObject};
+use raylib::prelude::{Color, RaylibDraw, Rectangle, Vector2};
+use std::{collections::HashMap, hash::Hash};
+
+/// Most cells a shape or query may cover, larger & non finite bounds skip the grid
+const MAX_CELLS: i64 = 4096;
+
+/// Uniform grid broad phase, shapes are stored by id in every cell
+/// their bounding box touches
+pub struct SpatialHash<Id> {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<Id>>,
+    /// Shapes too large for the grid, checked by every query
+    large: Vec<Id>,
+    bounds: HashMap<Id, Rectangle>,
+    layers: HashMap<Id, CollisionLayer>,
+}
+
+impl<Id: Copy + Eq + Hash> SpatialHash<Id> {
+    pub fn new(cell_size: f32) -> Self {
+        // confirm valid grid
+        assert!(cell_size > 0.0);
+
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            large: Vec::new(),
+            bounds: HashMap::new(),
+            layers: HashMap::new(),
+        }
+    }
+
+    pub const fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    pub fn contains(&self, id: Id) -> bool {
+        self.bounds.contains_key(&id)
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.large.clear();
+        self.bounds.clear();
+        self.layers.clear();
+    }
+
+    /// Insert shape, replaces any shape already stored with `id`
+    pub fn insert(&mut self, id: Id, shape: &impl CollisionObject) {
+        self.insert_bounds(id, shape.bounds(), shape.layer());
+    }
+
+    /// Insert bounding box on `layer`, replaces any shape already stored with `id`
+    pub fn insert_bounds(&mut self, id: Id, bounds: Rectangle, layer: CollisionLayer) {
+        self.remove(id);
+
+        match self.cell_range(&bounds) {
+            Some(range) => {
+                for cell in cells_in(range) {
+                    self.cells.entry(cell).or_default().push(id);
+                }
+            }
+            None => self.large.push(id),
+        }
+        self.bounds.insert(id, bounds);
+        self.layers.insert(id, layer);
+    }
+
+    /// Remove shape, returns false if `id` was not stored
+    pub fn remove(&mut self, id: Id) -> bool {
+        if let Some(bounds) = self.bounds.remove(&id) {
+            self.layers.remove(&id);
+            match self.cell_range(&bounds) {
+                Some(range) => {
+                    for cell in cells_in(range) {
+                        if let Some(ids) = self.cells.get_mut(&cell) {
+                            ids.retain(|x| *x != id);
+                            if ids.is_empty() {
+                                self.cells.remove(&cell);
+                            }
+                        }
+                    }
+                }
+                None => self.large.retain(|x| *x != id),
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Update stored bounds of a moved shape
+    pub fn move_shape(&mut self, id: Id, shape: &impl CollisionObject) {
+        let bounds = shape.bounds();
+
+        // only rehash when the occupied cells change
+        let same_cells = self
+            .bounds
+            .get(&id)
+            .is_some_and(|old| self.cell_range(old) == self.cell_range(&bounds));
+
+        if same_cells {
+            self.bounds.insert(id, bounds);
+            self.layers.insert(id, shape.layer());
+        } else {
+            self.insert_bounds(id, bounds, shape.layer());
+        }
+    }
+
+    /// Ids of all shapes on a `mask` layer whose bounding box overlaps `area`
+    pub fn query_rect(&self, area: Rectangle, mask: CollisionLayer) -> Vec<Id> {
+        let matches =
+            |id: &Id| mask.intersects(self.layers[id]) && overlaps(&self.bounds[id], &area);
+
+        // areas too large for the grid check every shape
+        let Some(range) = self.cell_range(&area) else {
+            return self
+                .bounds
+                .keys()
+                .filter(|id| matches(id))
+                .copied()
+                .collect();
+        };
+
+        let mut found = Vec::new();
+        for cell in cells_in(range) {
+            if let Some(ids) = self.cells.get(&cell) {
+                for id in ids {
+                    if !found.contains(id) && matches(id) {
+                        found.push(*id);
+                    }
+                }
+            }
+        }
+        found.extend(self.large.iter().filter(|id| matches(id)));
+        found
+    }
+
+    /// Ids of all shapes on a `mask` layer whose bounding box contains `point`
+    pub fn query_point(&self, point: Vector2, mask: CollisionLayer) -> Vec<Id> {
+        let cell = (self.cell_coord(point.x), self.cell_coord(point.y));
+        self.cells
+            .get(&cell)
+            .into_iter()
+            .flatten()
+            .chain(&self.large)
+            .filter(|id| {
+                mask.intersects(self.layers[*id]) && contains_point(&self.bounds[*id], point)
+            })
+            .copied()
+            .collect()
+    }
+}
+
+impl<Id: Copy + Eq + Hash> SpatialHash<Id> {
+    fn cell_coord(&self, value: f32) -> i32 {
+        (value / self.cell_size).floor() as i32
+    }
+
+    /// Min & max cell coordinates touched by `bounds`,
+    /// none when they're non finite or cover more than `MAX_CELLS`
+    fn cell_range(&self, bounds: &Rectangle) -> Option<CellRange> {
+        let finite = [bounds.x, bounds.y, bounds.width, bounds.height]
+            .iter()
+            .all(|value| value.is_finite());
+        if !finite {
+            return None;
+        }
+
+        let min = (self.cell_coord(bounds.x), self.cell_coord(bounds.y));
+        let max = (
+            self.cell_coord(bounds.x + bounds.width),
+            self.cell_coord(bounds.y + bounds.height),
+        );
+        let count = (max.0 as i64 - min.0 as i64 + 1) * (max.1 as i64 - min.1 as i64 + 1);
+        (count <= MAX_CELLS).then_some((min, max))
+    }
+}
+
+type CellRange = ((i32, i32), (i32, i32));
+
+fn cells_in(((min_x, min_y), (max_x, max_y)): CellRange) -> impl Iterator<Item = (i32, i32)> {
+    (min_x..=max_x).flat_map(move |x| (min_y..=max_y).map(move |y| (x, y)))
+}
+
+impl<Id> SpatialHash<Id> {
+    /// Draw occupied cells
+    pub fn draw(&self, color: Color, raylib: &mut impl RaylibDraw) {
+        for ((x, y), ids) in &self.cells {
+            let rect = Rectangle::new(
+                *x as f32 * self.cell_size,
+                *y as f32 * self.cell_size,
+                self.cell_size,
+                self.cell_size,
+            );
+
+            // outline
+            raylib.draw_rectangle_lines_ex(rect, 1, color.fade(0.25));
+
+            // shape count
+            raylib.draw_text(
+                &ids.len().to_string(),
+                rect.x as i32 + 4,
+                rect.y as i32 + 4,
+                10,
+                color,
+            );
+        }
+    }
+}
+
+fn contains_point(rect: &Rectangle, point: Vector2) -> bool {
+    point.x >= rect.x
+        && point.y >= rect.y
+        && point.x <= rect.x + rect.width
+        && point.y <= rect.y + rect.height
+}
@@ -0,0 +1,60 @@
+/// Accumulates frame time into fixed size simulation steps,
+/// leftover time is used to interpolate drawing between the last two steps
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedTimestep {
+    /// Seconds simulated per step
+    pub time_step: f32,
+    /// Most steps run in one frame, extra time is dropped
+    pub max_steps: u32,
+    accumulator: f32,
+}
+
+impl Default for FixedTimestep {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_TIME_STEP)
+    }
+}
+
+impl FixedTimestep {
+    pub const DEFAULT_TIME_STEP: f32 = 1.0 / 60.0;
+    /// Keeps a slow frame from snowballing into ever more steps
+    pub const DEFAULT_MAX_STEPS: u32 = 5;
+
+    pub const fn new(time_step: f32) -> Self {
+        Self {
+            time_step,
+            max_steps: Self::DEFAULT_MAX_STEPS,
+            accumulator: 0.0,
+        }
+    }
+
+    pub const fn with_max_steps(mut self, max_steps: u32) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Add a frame's time, returns how many steps to simulate
+    pub fn advance(&mut self, frame_time: f32) -> u32 {
+        self.accumulator += frame_time.max(0.0);
+
+        let steps = (self.accumulator / self.time_step) as u32;
+        self.accumulator -= steps as f32 * self.time_step;
+
+        // drop time that can't be caught up on
+        if steps > self.max_steps {
+            self.accumulator = 0.0;
+            return self.max_steps;
+        }
+        steps
+    }
+
+    /// Progress towards the next step [0, 1)
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / self.time_step).clamp(0.0, 1.0)
+    }
+
+    /// Forget leftover time
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+    }
+}
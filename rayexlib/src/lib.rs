@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 pub mod collision;
 pub mod debug;
+pub mod fixed_timestep;
 pub mod math;
 pub mod raylib_plugins;
 pub mod scene_manager;
@@ -16,6 +17,7 @@ pub mod prelude {
 
     pub use collision::*;
     pub use debug::*;
+    pub use fixed_timestep::*;
     pub use math::*;
     pub use raylib_plugins::*;
     pub use scene_manager::*;
@@ -1,5 +1,5 @@
 use super::{FrameLimiter, Math, Window};
-use raylib::prelude::{Camera2D, RaylibHandle, Vector2};
+use raylib::prelude::{Camera2D, RaylibHandle, Rectangle, Vector2};
 
 const ZOOM_MINIMUM: f32 = 0.5; // zoom out multiplier
 const ZOOM_MAXIMUM: f32 = 1.00; // zoom in multiplier
@@ -8,6 +8,8 @@ const ZOOM_STEP_AMOUNT: f32 = 64.00;
 pub struct CameraEx2D {
     camera: Camera2D,
     proj_zoom: f32,
+    /// Zoom keeping the area given to `fit` on screen
+    zoom_fit: f32,
     pub move_speed: f32,
     pub zoom_speed: f32,
     pub zoom_step: f32,
@@ -28,6 +30,7 @@ impl Default for CameraEx2D {
                 ..Default::default()
             },
             proj_zoom: 1.0,
+            zoom_fit: f32::INFINITY,
             zoom_speed: 1.0,
             zoom_step: 10.0,
             move_speed: 1.0,
@@ -141,11 +144,13 @@ impl CameraEx2D {
         // limit zoom
         self.proj_zoom = self.proj_zoom.clamp(self.zoom_minimum, self.zoom_maximum);
 
+        // zoom out further for a fitted area
+        let zoom = self.proj_zoom.min(self.zoom_fit).max(self.zoom_minimum);
+
         // smooth camera zoom
-        self.camera.zoom.lerp(
-            self.proj_zoom * self.zoom_scale,
-            self.zoom_speed * frame_time,
-        );
+        self.camera
+            .zoom
+            .lerp(zoom * self.zoom_scale, self.zoom_speed * frame_time);
 
         // camera shake
         if self.shaking {
@@ -162,6 +167,13 @@ impl CameraEx2D {
             .lerp(target, self.move_speed * frame_time);
     }
 
+    /// Zoom out so `area` stays on screen with `padding` around it, never past the zoom minimum
+    pub fn fit(&mut self, area: Rectangle, padding: f32, raylib: &RaylibHandle) {
+        let fit_x = raylib.get_screen_width() as f32 / (area.width + padding * 2.0);
+        let fit_y = raylib.get_screen_height() as f32 / (area.height + padding * 2.0);
+        self.zoom_fit = fit_x.min(fit_y) / self.zoom_scale;
+    }
+
     /// Speed up camera move speed for fast velocities
     pub fn follow_bound(&mut self, velocity: Vector2, damping: f32, max: f32) {
         self.move_speed = (velocity.length() / damping).max(max);
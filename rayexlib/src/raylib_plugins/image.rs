@@ -1,9 +1,30 @@
 use super::MISSING_TEXTURE;
-use raylib::prelude::Image;
+use crate::collision::{AlphaMask, Polygon2D, Rect2D};
+use raylib::prelude::{Color, Image, Rectangle, Vector2};
 
 pub trait ImagePlugin {
     fn from_path(path: &str) -> Image;
     fn from_paths(paths: Vec<&str>) -> Vec<Image>;
+
+    /// Pixels of `region` with alpha above `threshold` (clipped to the image)
+    fn alpha_mask(&self, region: Rectangle, threshold: u8) -> AlphaMask;
+
+    /// Tight box around the opaque pixels, relative to the region's top left
+    fn alpha_rect(&self, region: Rectangle, threshold: u8) -> Option<Rect2D> {
+        self.alpha_mask(region, threshold).rect()
+    }
+
+    /// Traced opaque outline simplified by `tolerance` pixels [region center at (0,0)]
+    fn alpha_polygon(&self, region: Rectangle, threshold: u8, tolerance: f32) -> Option<Polygon2D> {
+        self.alpha_mask(region, threshold).polygon(tolerance)
+    }
+
+    /// One mask per frame of a horizontal animation strip
+    fn frame_masks(&self, frames: u32, threshold: u8) -> Vec<AlphaMask>;
+
+    /// Horizontal strip of `frames` cells from a grid sheet, starting at cell `first`
+    /// (cells counted in reading order)
+    fn sheet_strip(&self, frame_size: Vector2, first: u32, frames: u32) -> Image;
 }
 
 impl ImagePlugin for Image {
@@ -42,4 +63,61 @@ impl ImagePlugin for Image {
         }
         images
     }
+
+    fn alpha_mask(&self, region: Rectangle, threshold: u8) -> AlphaMask {
+        let x = (region.x.max(0.0) as usize).min(self.width as usize);
+        let y = (region.y.max(0.0) as usize).min(self.height as usize);
+        let width = ((region.x + region.width).max(0.0) as usize)
+            .min(self.width as usize)
+            .saturating_sub(x);
+        let height = ((region.y + region.height).max(0.0) as usize)
+            .min(self.height as usize)
+            .saturating_sub(y);
+
+        let colors = self.get_image_data();
+        let mut opaque = Vec::with_capacity(width * height);
+        for row in y..y + height {
+            for column in x..x + width {
+                opaque.push(colors[row * self.width as usize + column].a > threshold);
+            }
+        }
+        AlphaMask::new(width, height, opaque)
+    }
+
+    fn frame_masks(&self, frames: u32, threshold: u8) -> Vec<AlphaMask> {
+        let frame_width = self.width as f32 / frames.max(1) as f32;
+        (0..frames)
+            .map(|frame| {
+                let region = Rectangle::new(
+                    frame as f32 * frame_width,
+                    0.0,
+                    frame_width,
+                    self.height as f32,
+                );
+                self.alpha_mask(region, threshold)
+            })
+            .collect()
+    }
+
+    fn sheet_strip(&self, frame_size: Vector2, first: u32, frames: u32) -> Image {
+        let columns = ((self.width as f32 / frame_size.x) as u32).max(1);
+        let mut strip = Image::gen_image_color(
+            (frame_size.x * frames as f32) as i32,
+            frame_size.y as i32,
+            Color::BLANK,
+        );
+
+        for frame in 0..frames {
+            let cell = first + frame;
+            let source = Rectangle::new(
+                (cell % columns) as f32 * frame_size.x,
+                (cell / columns) as f32 * frame_size.y,
+                frame_size.x,
+                frame_size.y,
+            );
+            let dest = Rectangle::new(frame as f32 * frame_size.x, 0.0, frame_size.x, frame_size.y);
+            strip.draw(self, source, dest, Color::WHITE);
+        }
+        strip
+    }
 }
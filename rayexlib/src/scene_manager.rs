@@ -4,8 +4,18 @@ use std::{collections::HashMap, fmt::Debug, hash::Hash};
 pub trait Scene<GlobalEnv> {
     fn on_enter(&mut self, _global_env: &mut GlobalEnv, _raylib: &mut RaylibHandle) {}
     fn on_exit(&mut self, _global_env: &mut GlobalEnv, _raylib: &mut RaylibHandle) {}
+    /// Once per simulation step, `time_step` seconds long
+    fn fixed_update(
+        &mut self,
+        _global_env: &mut GlobalEnv,
+        _raylib: &mut RaylibHandle,
+        _time_step: f32,
+    ) {
+    }
+    /// Once per rendered frame, after the frame's simulation steps
     fn update(&mut self, global_env: &mut GlobalEnv, raylib: &mut RaylibHandle);
-    fn draw(&self, global_env: &GlobalEnv, raylib: &mut RaylibDrawHandle);
+    /// `alpha` is the progress [0, 1) from the last simulation step to the next
+    fn draw(&self, global_env: &GlobalEnv, raylib: &mut RaylibDrawHandle, alpha: f32);
     fn debug(&self, _global_env: &GlobalEnv, _raylib: &mut RaylibDrawHandle) {}
 }
 
@@ -64,6 +74,19 @@ where
         }
     }
 
+    pub fn fixed_update(
+        &mut self,
+        global_env: &mut GlobalEnv,
+        raylib: &mut RaylibHandle,
+        time_step: f32,
+    ) {
+        if let Some(scene) = self.scenes.get_mut(&self.id) {
+            scene.fixed_update(global_env, raylib, time_step);
+        } else {
+            panic!("Invalid scene: {:?}", self.id);
+        }
+    }
+
     pub fn update(&mut self, global_env: &mut GlobalEnv, raylib: &mut RaylibHandle) {
         if let Some(scene) = self.scenes.get_mut(&self.id) {
             scene.update(global_env, raylib);
@@ -72,9 +95,9 @@ where
         }
     }
 
-    pub fn draw(&self, global_env: &GlobalEnv, raylib: &mut RaylibDrawHandle) {
+    pub fn draw(&self, global_env: &GlobalEnv, raylib: &mut RaylibDrawHandle, alpha: f32) {
         if let Some(scene) = self.scenes.get(&self.id) {
-            scene.draw(global_env, raylib);
+            scene.draw(global_env, raylib, alpha);
         } else {
             panic!("Invalid scene: {:?}", self.id);
         }
@@ -1,37 +1,86 @@
-use crate::prelude::Timer;
+use crate::prelude::AlphaMask;
 use raylib::prelude::{Color, RaylibDraw, Rectangle, Texture2D, Vector2};
+use std::rc::Rc;
 
 pub struct AnimationStrip {
     frame: u32,
     frames: u32,
-    fps_timer: Timer,
+    /// Seconds each frame is shown
+    frame_time: f32,
+    /// Simulated seconds into the current frame
+    elapsed: f32,
     texture_strip: Texture2D,
+    /// Opaque pixels of each frame, empty unless set
+    masks: Vec<Rc<AlphaMask>>,
+    /// Frame size when it differs from the player's sprite size
+    frame_size: Option<Vector2>,
+    /// Start over after the last frame, otherwise hold it
+    looping: bool,
 }
 
 impl AnimationStrip {
-    pub fn new(frames: u32, fps_timer: Timer, texture_strip: Texture2D) -> AnimationStrip {
+    pub fn new(frames: u32, fps: f32, texture_strip: Texture2D) -> AnimationStrip {
         AnimationStrip {
             frame: 0,
             frames,
-            fps_timer,
+            frame_time: 1.0 / fps,
+            elapsed: 0.0,
             texture_strip,
+            masks: Vec::new(),
+            frame_size: None,
+            looping: true,
         }
     }
 
+    /// Frames of a different size than the sprite, anchored at its bottom center
+    pub fn with_frame_size(mut self, frame_size: Vector2) -> Self {
+        self.frame_size = Some(frame_size);
+        self
+    }
+
+    pub fn frame_size(&self) -> Option<Vector2> {
+        self.frame_size
+    }
+
+    pub fn set_masks(&mut self, masks: Vec<AlphaMask>) {
+        self.masks = masks.into_iter().map(Rc::new).collect();
+    }
+
+    /// Mask of the current frame
+    pub fn mask(&self) -> Option<Rc<AlphaMask>> {
+        self.masks.get(self.frame as usize).cloned()
+    }
+
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
     pub fn set_fps(&mut self, fps: f32) {
-        self.fps_timer.set_wait_time(1.0 / fps);
+        self.frame_time = 1.0 / fps;
+    }
+
+    pub fn frame(&self) -> u32 {
+        self.frame
     }
 
+    /// Back to the first frame, shown for a full frame time
     pub fn reset_frame(&mut self) {
         self.frame = 0;
+        self.elapsed = 0.0;
     }
 
-    pub fn next_frame(&mut self, source_rect: &mut Rectangle) {
-        if self.fps_timer.is_finished() {
-            self.frame = (self.frame + 1) % self.frames;
-            source_rect.x = self.frame as f32 * source_rect.width.abs();
-            self.fps_timer.start();
+    /// Advance by `delta` simulated seconds, skipping frames on long steps
+    pub fn next_frame(&mut self, delta: f32, source_rect: &mut Rectangle) {
+        self.elapsed += delta;
+        while self.elapsed >= self.frame_time {
+            self.elapsed -= self.frame_time;
+            self.frame = if self.looping {
+                (self.frame + 1) % self.frames
+            } else {
+                (self.frame + 1).min(self.frames - 1)
+            };
         }
+        source_rect.x = self.frame as f32 * source_rect.width.abs();
     }
 
     pub fn draw_with(
@@ -1,34 +1,84 @@
-use super::{AnimationPlayer2D, Texture2DPlugin};
-use raylib::prelude::{RaylibHandle, RaylibThread, Texture2D, Vector2};
+use super::{AnimationPlayer2D, AnimationStrip, Texture2DPlugin};
+use crate::prelude::ImagePlugin;
+use raylib::prelude::{Image, RaylibHandle, RaylibThread, Texture2D, Vector2};
 use std::hash::Hash;
 
 pub struct AnimationMachineBuilder<T> {
     animation_machine: AnimationPlayer2D<T>,
+    sprite_size: Vector2,
     states: Vec<T>,
     texture_strip_paths: Vec<String>,
+    /// First sheet cell of animations cut from a grid sheet
+    sheet_cells: Vec<Option<u32>>,
+    /// Frame size of animations not sized like the sprite
+    frame_sizes: Vec<Option<Vector2>>,
     frame_amounts: Vec<u32>,
     fps_values: Vec<f32>,
+    mask_threshold: Option<u8>,
 }
 
 impl<T: Hash + Eq> AnimationMachineBuilder<T> {
     pub fn new(sprite_size: Vector2) -> Self {
         Self {
             animation_machine: AnimationPlayer2D::new(sprite_size),
+            sprite_size,
             states: Vec::new(),
             texture_strip_paths: Vec::new(),
+            sheet_cells: Vec::new(),
+            frame_sizes: Vec::new(),
             frame_amounts: Vec::new(),
             fps_values: Vec::new(),
+            mask_threshold: None,
         }
     }
 
+    /// Also build pixel masks of every frame, from pixels with alpha above `threshold`
+    pub fn add_masks(&mut self, threshold: u8) {
+        self.mask_threshold = Some(threshold);
+    }
+
     /// Insert new animations
     pub fn add_animation(&mut self, state: T, texture_strip_path: &str, frames: u32, fps: f32) {
         self.states.push(state);
         self.texture_strip_paths.push(texture_strip_path.into());
+        self.sheet_cells.push(None);
+        self.frame_sizes.push(None);
         self.frame_amounts.push(frames);
         self.fps_values.push(fps);
     }
 
+    /// Insert an animation of `frames` cells from a grid sheet of sprite sized cells,
+    /// starting at cell `first` (cells counted in reading order)
+    pub fn add_sheet_animation(
+        &mut self,
+        state: T,
+        sheet_path: &str,
+        first: u32,
+        frames: u32,
+        fps: f32,
+    ) {
+        self.add_animation(state, sheet_path, frames, fps);
+        if let Some(cell) = self.sheet_cells.last_mut() {
+            *cell = Some(first);
+        }
+    }
+
+    /// Insert an animation with frames of `frame_size` instead of the sprite size,
+    /// drawn at the same scale & anchored at the sprite's bottom center
+    pub fn add_sized_animation(
+        &mut self,
+        state: T,
+        texture_strip_path: &str,
+        frame_size: Vector2,
+        frames: u32,
+        fps: f32,
+    ) {
+        self.add_animation(state, texture_strip_path, frames, fps);
+        if let Some(size) = self.frame_sizes.last_mut() {
+            *size = Some(frame_size);
+        }
+    }
+
     /// Build AnimationPlayer with all added animations
     pub fn build(
         mut self,
@@ -36,14 +86,33 @@ impl<T: Hash + Eq> AnimationMachineBuilder<T> {
         thread: &RaylibThread,
     ) -> AnimationPlayer2D<T> {
         for _ in 0..self.states.len() {
-            let texture_strip =
-                Texture2D::from_path(raylib, thread, &self.texture_strip_paths.remove(0));
-            self.animation_machine.add_animation(
-                self.states.remove(0),
-                texture_strip,
-                self.frame_amounts.remove(0),
-                self.fps_values.remove(0),
-            );
+            let path = self.texture_strip_paths.remove(0);
+            let frames = self.frame_amounts.remove(0);
+            let fps = self.fps_values.remove(0);
+
+            // sheet animations are cut into a strip first
+            let strip = self
+                .sheet_cells
+                .remove(0)
+                .map(|first| Image::from_path(&path).sheet_strip(self.sprite_size, first, frames));
+            let texture_strip = match &strip {
+                Some(strip) => raylib
+                    .load_texture_from_image(thread, strip)
+                    .expect("RAYLIB: Could not load texture from sheet!"),
+                None => Texture2D::from_path(raylib, thread, &path),
+            };
+            let mut anim_strip = AnimationStrip::new(frames, fps, texture_strip);
+            if let Some(frame_size) = self.frame_sizes.remove(0) {
+                anim_strip = anim_strip.with_frame_size(frame_size);
+            }
+
+            if let Some(threshold) = self.mask_threshold {
+                let image = strip.unwrap_or_else(|| Image::from_path(&path));
+                anim_strip.set_masks(image.frame_masks(frames, threshold));
+            }
+
+            self.animation_machine
+                .insert_animation(self.states.remove(0), anim_strip);
         }
         self.animation_machine
     }
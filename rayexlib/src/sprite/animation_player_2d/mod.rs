@@ -1,5 +1,5 @@
-use crate::prelude::{SpriteTransform, Texture2DPlugin, Timer};
-use raylib::prelude::{Color, RaylibDraw, Texture2D, Vector2};
+use crate::prelude::{AlphaMask, Bitmask2D, SpriteTransform, Texture2DPlugin};
+use raylib::prelude::{Color, RaylibDraw, Rectangle, Texture2D, Vector2};
 use std::{collections::HashMap, hash::Hash};
 
 mod animation_strip_2d;
@@ -22,41 +22,134 @@ impl<State: Hash + Eq> AnimationPlayer2D<State> {
     }
 
     pub fn add_animation(&mut self, state: State, texture_strip: Texture2D, frames: u32, fps: f32) {
-        let fps_timer = Timer::from_secs_f32(1.0 / fps);
-        let anim_strip = AnimationStrip::new(frames, fps_timer, texture_strip);
+        let anim_strip = AnimationStrip::new(frames, fps, texture_strip);
+        self.insert_animation(state, anim_strip);
+    }
+
+    /// Insert an already built animation strip
+    pub fn insert_animation(&mut self, state: State, anim_strip: AnimationStrip) {
         self.animations.insert(state, anim_strip);
     }
 
+    /// Pixel masks for each frame of an animation
+    pub fn set_masks(&mut self, state: &State, masks: Vec<AlphaMask>) {
+        if let Some(anim_strip) = self.animations.get_mut(state) {
+            anim_strip.set_masks(masks);
+        }
+    }
+
+    /// Pixel collider of the current frame, placed & flipped like the drawn sprite
+    pub fn bitmask(&self, state: &State) -> Option<Bitmask2D> {
+        let anim_strip = self.animations.get(state)?;
+        let bits = anim_strip.mask()?;
+        let (source, rect, offset) = self.placement(anim_strip, self.transform.rect);
+        let scale = Vector2::new(
+            rect.width / bits.width() as f32,
+            rect.height / bits.height() as f32,
+        );
+
+        Some(
+            Bitmask2D::new(bits)
+                .with_position(rect.x - offset.x, rect.y - offset.y)
+                .with_scale(scale.x, scale.y)
+                .with_flip(source.width < 0.0, source.height < 0.0),
+        )
+    }
+
+    /// Play an animation once, holding its last frame
+    pub fn set_looping(&mut self, state: &State, looping: bool) {
+        if let Some(anim_strip) = self.animations.get_mut(state) {
+            anim_strip.set_looping(looping);
+        }
+    }
+
     pub fn set_fps(&mut self, state: State, fps: f32) {
         if let Some(anim_strip) = self.animations.get_mut(&state) {
             anim_strip.set_fps(fps);
         }
     }
 
+    /// Current frame of an animation, 0 if it doesn't exist
+    pub fn frame(&self, state: &State) -> u32 {
+        self.animations
+            .get(state)
+            .map_or(0, |anim_strip| anim_strip.frame())
+    }
+
     pub fn reset_frame(&mut self, state: &State) {
         if let Some(anim_strip) = self.animations.get_mut(state) {
             anim_strip.reset_frame();
+            self.transform.source_rect.x = 0.0;
         }
     }
 
-    pub fn next_frame(&mut self, current_state: &State) {
+    /// Advance the current animation by `delta` simulated seconds
+    pub fn next_frame(&mut self, current_state: &State, delta: f32) {
         if let Some(anim_strip) = self.animations.get_mut(current_state) {
-            anim_strip.next_frame(&mut self.transform.source_rect);
+            anim_strip.next_frame(delta, &mut self.transform.source_rect);
         }
     }
 
     pub fn draw(&self, state: &State, raylib: &mut impl RaylibDraw) {
         if let Some(anim_strip) = self.animations.get(state) {
+            let (source, rect, offset) = self.placement(anim_strip, self.transform.rect);
             anim_strip.draw_with(
                 raylib,
-                self.transform.source_rect,
-                self.transform.rect,
-                self.transform.offset,
+                source,
+                rect,
+                offset,
                 self.transform.rotation,
                 self.transform.tint,
             );
         }
     }
+
+    /// Draw as if positioned at `position`
+    pub fn draw_at(&self, state: &State, position: Vector2, raylib: &mut impl RaylibDraw) {
+        if let Some(anim_strip) = self.animations.get(state) {
+            let mut rect = self.transform.rect;
+            rect.x = position.x;
+            rect.y = position.y;
+
+            let (source, rect, offset) = self.placement(anim_strip, rect);
+            anim_strip.draw_with(
+                raylib,
+                source,
+                rect,
+                offset,
+                self.transform.rotation,
+                self.transform.tint,
+            );
+        }
+    }
+
+    /// Source, destination & offset of a strip's current frame drawn at `rect`
+    /// (differently sized frames keep the sprite's bottom center in place)
+    fn placement(
+        &self,
+        anim_strip: &AnimationStrip,
+        mut rect: Rectangle,
+    ) -> (Rectangle, Rectangle, Vector2) {
+        let source = self.transform.source_rect;
+        let Some(size) = anim_strip.frame_size() else {
+            return (source, rect, self.transform.offset);
+        };
+
+        let width = size.x * rect.width / source.width.abs();
+        let height = size.y * rect.height / source.height.abs();
+        let offset =
+            self.transform.offset + Vector2::new((width - rect.width) / 2.0, height - rect.height);
+        rect.width = width;
+        rect.height = height;
+
+        let source = Rectangle::new(
+            anim_strip.frame() as f32 * size.x,
+            0.0,
+            size.x.copysign(source.width),
+            size.y.copysign(source.height),
+        );
+        (source, rect, offset)
+    }
 }
 
 /// Export transform getters
@@ -0,0 +1,70 @@
+use super::{FrameHitboxes, Hitbox};
+
+/// Part of an attack, hitboxes are only out while active
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackPhase {
+    Startup,
+    Active,
+    Recovery,
+}
+
+/// Frame data of one attack, timed in animation frames
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttackMove {
+    pub fps: f32,
+    pub startup: u32,
+    pub active: u32,
+    pub recovery: u32,
+    /// Boxes for each active frame
+    pub hitboxes: FrameHitboxes,
+}
+
+impl AttackMove {
+    pub fn new(
+        fps: f32,
+        startup: u32,
+        active: u32,
+        recovery: u32,
+        hitboxes: FrameHitboxes,
+    ) -> Self {
+        Self {
+            fps,
+            startup,
+            active,
+            recovery,
+            hitboxes,
+        }
+    }
+
+    /// Total frames, also the length of the animation
+    pub fn frames(&self) -> u32 {
+        self.startup + self.active + self.recovery
+    }
+
+    /// Frame `elapsed` seconds into the attack
+    pub fn frame(&self, elapsed: f32) -> u32 {
+        (elapsed * self.fps) as u32
+    }
+
+    /// Phase `elapsed` seconds into the attack, none once it's over
+    pub fn phase(&self, elapsed: f32) -> Option<AttackPhase> {
+        let frame = self.frame(elapsed);
+        if frame < self.startup {
+            Some(AttackPhase::Startup)
+        } else if frame < self.startup + self.active {
+            Some(AttackPhase::Active)
+        } else if frame < self.frames() {
+            Some(AttackPhase::Recovery)
+        } else {
+            None
+        }
+    }
+
+    /// Boxes out `elapsed` seconds into the attack
+    pub fn hitboxes(&self, elapsed: f32) -> &[Hitbox] {
+        match self.phase(elapsed) {
+            Some(AttackPhase::Active) => self.hitboxes.frame(self.frame(elapsed) - self.startup),
+            _ => &[],
+        }
+    }
+}
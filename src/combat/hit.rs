@@ -0,0 +1,133 @@
+use super::{ActiveHitbox, AttackData};
+use rayexlib::prelude::{Bitmask2D, CollisionObject, Rect2D};
+use raylib::prelude::Vector2;
+
+/// One attack landing on a target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HitEvent<Id> {
+    pub attacker: Id,
+    pub target: Id,
+    pub data: AttackData,
+    /// Attacker's facing, 1 right & -1 left
+    pub facing: f32,
+}
+
+impl<Id> HitEvent<Id> {
+    /// Launch velocity given to the target
+    pub fn launch(&self) -> Vector2 {
+        self.data.launch(self.facing)
+    }
+}
+
+/// Boxes an attacker has out this step
+pub struct Attacker<'a, Id> {
+    pub id: Id,
+    pub facing: f32,
+    pub hitboxes: &'a [ActiveHitbox],
+    /// Targets this attack already landed on
+    pub already_hit: &'a [Id],
+    /// Boxes can clash with other attacks, hazards can't be stopped
+    pub clashes: bool,
+}
+
+/// Attacks resolved in one step
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttackResults<Id> {
+    pub hits: Vec<HitEvent<Id>>,
+    /// Attackers that lost a clash (both on a tie), their attack should end
+    pub cancelled: Vec<Id>,
+}
+
+/// Body that can be hit, optionally refined by a pixel mask
+pub struct Hurtbox<'a, Id> {
+    pub id: Id,
+    pub rect: &'a Rect2D,
+    pub pixels: Option<Bitmask2D>,
+}
+
+impl<Id> Hurtbox<'_, Id> {
+    fn hit_by(&self, hitbox: &ActiveHitbox) -> bool {
+        hitbox.rect.intersects(self.rect)
+            && self
+                .pixels
+                .as_ref()
+                .is_none_or(|pixels| pixels.overlaps_rect(&hitbox.rect))
+    }
+}
+
+/// Hits landed this step, at most one per attacker & target
+/// (clashing hitboxes cancel the lower priority attack, or both on a tie)
+pub fn detect_hits<Id: Copy + PartialEq>(
+    attackers: &[Attacker<Id>],
+    hurtboxes: &[Hurtbox<Id>],
+) -> AttackResults<Id> {
+    let priority = |attacker: &Attacker<Id>| {
+        attacker
+            .hitboxes
+            .iter()
+            .map(|hitbox| hitbox.data.priority)
+            .max()
+    };
+
+    // clashes
+    let mut cancelled = vec![false; attackers.len()];
+    for (i, a) in attackers.iter().enumerate() {
+        for (j, b) in attackers.iter().enumerate().skip(i + 1) {
+            let clashing = a.clashes
+                && b.clashes
+                && a.hitboxes.iter().any(|hitbox_a| {
+                    b.hitboxes
+                        .iter()
+                        .any(|hitbox_b| clash(&hitbox_a.rect, &hitbox_b.rect))
+                });
+            if !clashing {
+                continue;
+            }
+
+            let (priority_a, priority_b) = (priority(a), priority(b));
+            cancelled[i] |= priority_a <= priority_b;
+            cancelled[j] |= priority_b <= priority_a;
+        }
+    }
+
+    let mut hits = Vec::new();
+    for (attacker, _) in attackers.iter().zip(&cancelled).filter(|(_, c)| !**c) {
+        for hurtbox in hurtboxes {
+            if hurtbox.id == attacker.id || attacker.already_hit.contains(&hurtbox.id) {
+                continue;
+            }
+
+            // strongest box touching the target
+            let hitbox = attacker
+                .hitboxes
+                .iter()
+                .filter(|hitbox| hurtbox.hit_by(hitbox))
+                .max_by_key(|hitbox| hitbox.data.priority);
+
+            if let Some(hitbox) = hitbox {
+                hits.push(HitEvent {
+                    attacker: attacker.id,
+                    target: hurtbox.id,
+                    data: hitbox.data,
+                    facing: attacker.facing,
+                });
+            }
+        }
+    }
+
+    let cancelled = attackers
+        .iter()
+        .zip(&cancelled)
+        .filter(|(_, c)| **c)
+        .map(|(attacker, _)| attacker.id)
+        .collect();
+    AttackResults { hits, cancelled }
+}
+
+/// Hitboxes only detect hurtboxes, so clashes compare the boxes directly
+fn clash(a: &Rect2D, b: &Rect2D) -> bool {
+    a.position.x < b.position.x + b.size.x
+        && b.position.x < a.position.x + a.size.x
+        && a.position.y < b.position.y + b.size.y
+        && b.position.y < a.position.y + a.size.y
+}
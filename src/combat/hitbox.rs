@@ -0,0 +1,93 @@
+use crate::layers;
+use rayexlib::prelude::Rect2D;
+use raylib::prelude::Vector2;
+
+/// What an attack does to whoever it hits
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttackData {
+    pub damage: f32,
+    /// Launch speed given to the target
+    pub knockback: f32,
+    /// Launch direction in degrees, 0 is forward & 90 is straight up
+    pub angle: f32,
+    /// Seconds the target can't act
+    pub hitstun: f32,
+    /// Higher priority wins clashes & overlapping boxes of one attack
+    pub priority: u8,
+}
+
+impl AttackData {
+    pub const fn new(damage: f32, knockback: f32, angle: f32, hitstun: f32) -> Self {
+        Self {
+            damage,
+            knockback,
+            angle,
+            hitstun,
+            priority: 0,
+        }
+    }
+
+    pub const fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Launch velocity for an attacker facing `facing` (1 right, -1 left)
+    pub fn launch(&self, facing: f32) -> Vector2 {
+        let angle = self.angle.to_radians();
+        Vector2::new(angle.cos() * facing, -angle.sin()) * self.knockback
+    }
+}
+
+/// Attack box relative to its owner's top left, facing right
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hitbox {
+    pub offset: Vector2,
+    pub size: Vector2,
+    pub data: AttackData,
+}
+
+impl Hitbox {
+    pub const fn new(offset: Vector2, size: Vector2, data: AttackData) -> Self {
+        Self { offset, size, data }
+    }
+
+    /// World box for an owner at `origin` `width` wide, mirrored when facing left
+    pub fn placed(&self, origin: Vector2, width: f32, facing: f32) -> Rect2D {
+        let x = if facing < 0.0 {
+            width - self.offset.x - self.size.x
+        } else {
+            self.offset.x
+        };
+
+        Rect2D::newv(self.size)
+            .with_position(origin.x + x, origin.y + self.offset.y)
+            .with_layer(layers::HITBOX)
+            .with_mask(layers::HITBOX_MASK)
+    }
+}
+
+/// Hitboxes for each frame of an attack animation
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrameHitboxes {
+    frames: Vec<Vec<Hitbox>>,
+}
+
+impl FrameHitboxes {
+    pub fn new(frames: Vec<Vec<Hitbox>>) -> Self {
+        Self { frames }
+    }
+
+    /// Boxes out on `frame`, none past the last frame
+    pub fn frame(&self, frame: u32) -> &[Hitbox] {
+        self.frames
+            .get(frame as usize)
+            .map_or(&[], |boxes| boxes.as_slice())
+    }
+}
+
+/// Hitbox placed in the world this step
+pub struct ActiveHitbox {
+    pub rect: Rect2D,
+    pub data: AttackData,
+}
@@ -0,0 +1,7 @@
+mod attack;
+mod hit;
+mod hitbox;
+
+pub use attack::{AttackMove, AttackPhase};
+pub use hit::{detect_hits, AttackResults, Attacker, HitEvent, Hurtbox};
+pub use hitbox::{ActiveHitbox, AttackData, FrameHitboxes, Hitbox};
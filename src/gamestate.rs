@@ -3,7 +3,7 @@ use crate::{
     scenes::{GlobalEnvironment, SceneID, SceneInitializer},
 };
 use rayexlib::prelude::{
-    Debug, DebugTools, FrameLimiter, ImagePlugin, SceneManager, Toggle, Window,
+    Debug, DebugTools, FixedTimestep, FrameLimiter, ImagePlugin, SceneManager, Toggle, Window,
 };
 use raylib::prelude::{
     Color, Image, KeyboardKey, MouseCursor, RaylibDraw, RaylibDrawHandle, RaylibHandle,
@@ -14,6 +14,7 @@ pub struct GameState {
     window: Window,
     scene_machine: SceneManager<SceneID, GlobalEnvironment>,
     global_env: GlobalEnvironment,
+    timestep: FixedTimestep,
     paused: bool,
     exit: bool,
     debug: Debug,
@@ -25,6 +26,7 @@ impl GameState {
             window: Window::new(),
             scene_machine: SceneManager::init(raylib, thread),
             global_env: GlobalEnvironment::init(raylib, thread),
+            timestep: FixedTimestep::default(),
             paused: false,
             exit: false,
             debug: Debug::default(),
@@ -95,14 +97,43 @@ impl GameState {
         self.global_update(raylib);
 
         if !self.paused && !self.debug.paused && raylib.is_window_focused() {
+            // step frames simulate exactly one step
+            let steps = if self.debug.step_frames {
+                self.timestep.reset();
+                1
+            } else {
+                self.timestep.advance(raylib.get_frame_time())
+            };
+
+            // current scene simulation
+            for _ in 0..steps {
+                self.scene_machine.fixed_update(
+                    &mut self.global_env,
+                    raylib,
+                    self.timestep.time_step,
+                );
+
+                // leave the scene before it simulates any further
+                if self.global_env.next_scene.is_some() {
+                    break;
+                }
+            }
+
             // current scene update
             self.scene_machine.update(&mut self.global_env, raylib);
+
+            // scene switch requested by the scene
+            if let Some(next_scene) = self.global_env.next_scene.take() {
+                self.scene_machine
+                    .next_scene(&mut self.global_env, raylib, next_scene);
+            }
         }
     }
 
     fn draw(&self, raylib: &mut RaylibDrawHandle) {
-        // current scene draw
-        self.scene_machine.draw(&self.global_env, raylib);
+        // current scene draw, between the last two simulation steps
+        self.scene_machine
+            .draw(&self.global_env, raylib, self.timestep.alpha());
 
         // pause screen
         if self.paused {
@@ -189,6 +220,10 @@ impl GameState {
                     self.scene_machine
                         .next_scene(&mut self.global_env, raylib, SceneID::PauseMenu)
                 }
+                KeyboardKey::KEY_FIVE => {
+                    self.scene_machine
+                        .next_scene(&mut self.global_env, raylib, SceneID::EndMenu)
+                }
                 _ => {}
             }
 
@@ -265,6 +300,14 @@ impl DebugTools for GameState {
                 Color::YELLOW,
                 &format!("frame time: {}", raylib.get_frame_time_limited()),
             ),
+            (
+                Color::SKYBLUE,
+                &format!(
+                    "time step: {} (alpha: {:.2})",
+                    self.timestep.time_step,
+                    self.timestep.alpha()
+                ),
+            ),
             (
                 Color::BEIGE,
                 &format!("current scene: {:?}", self.scene_machine.id),
@@ -0,0 +1,15 @@
+use rayexlib::prelude::CollisionLayer;
+
+// shapes without a layer are terrain
+pub const TERRAIN: CollisionLayer = CollisionLayer::DEFAULT;
+pub const PLAYER: CollisionLayer = CollisionLayer::bit(1);
+pub const HITBOX: CollisionLayer = CollisionLayer::bit(2);
+pub const HURTBOX: CollisionLayer = CollisionLayer::bit(3);
+pub const PROJECTILE: CollisionLayer = CollisionLayer::bit(4);
+pub const TRIGGER: CollisionLayer = CollisionLayer::bit(5);
+
+// what each kind of shape detects
+pub const PLAYER_MASK: CollisionLayer = TERRAIN;
+pub const HITBOX_MASK: CollisionLayer = HURTBOX;
+pub const PROJECTILE_MASK: CollisionLayer = TERRAIN.union(HURTBOX);
+pub const TRIGGER_MASK: CollisionLayer = PLAYER.union(PROJECTILE);
@@ -1,10 +1,13 @@
 #![allow(unused_variables, dead_code)]
 //#![windows_subsystem = "windows"]
 
+mod combat;
 mod gamestate;
+mod layers;
 mod paths;
 mod player;
 mod scenes;
+mod versus;
 
 fn main() {
     // raylib setup
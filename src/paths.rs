@@ -19,6 +19,8 @@ pub mod player {
         pub const FALL: &str = "resources/player/basic/jump_48x48.png";
         pub const CROUCH_IDLE: &str = "resources/player/basic/crouch_idle_48x48.png";
         pub const CROUCH_WALK: &str = "resources/player/basic/crouch_walk_48x48.png";
+        pub const HURT: &str = "resources/player/basic/hurt_48x48.png";
+        pub const DEATH: &str = "resources/player/basic/death_64x64.png";
     }
 
     pub mod advn {
@@ -31,11 +33,13 @@ pub mod player {
         pub const CRID: &str = "resources/player/adventurer/crouch_idle.png";
         pub const CRWK: &str = "resources/player/adventurer/crouch_walk.png";
         pub const WSLD: &str = "resources/player/adventurer/wall_slide.png";
+        pub const HAND_COMBAT: &str = "resources/player/adventurer-hand-combat-Sheet.png";
     }
 }
 
 pub mod world {
     pub const TILESET: &str = "resources/tiles/mainlev_build.png";
+    pub const PROPS: &str = "resources/tiles/props1.png";
 }
 
 pub const MAPPING: &str = "resources/glfw/gamecontrollerdb.txt";
@@ -0,0 +1,74 @@
+use super::{Player, PlayerState};
+use crate::combat::{AttackData, AttackMove, FrameHitboxes, Hitbox};
+use raylib::prelude::Vector2;
+
+/// Attack of each attack state, boxes relative to the player's collider
+pub struct Attacks {
+    /// Lunging punch
+    pub ground: AttackMove,
+    /// Diving kick
+    pub air: AttackMove,
+    /// Low kick, relative to the crouched collider
+    pub crouch: AttackMove,
+}
+
+impl Attacks {
+    // first cells in the hand combat sheet
+    pub const GROUND_CELL: u32 = 14;
+    pub const AIR_CELL: u32 = 28;
+    pub const CROUCH_CELL: u32 = 24;
+
+    /// Attack played by `state`, none for movement states
+    pub fn get(&self, state: &PlayerState) -> Option<&AttackMove> {
+        match state {
+            PlayerState::Attacking => Some(&self.ground),
+            PlayerState::AirAttacking => Some(&self.air),
+            PlayerState::CrouchAttacking => Some(&self.crouch),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Attacks {
+    fn default() -> Self {
+        let punch = Hitbox::new(
+            Vector2::new(55.0, 20.0),
+            Vector2::new(75.0, 35.0),
+            AttackData::new(8.0, 450.0, 30.0, 0.3),
+        );
+        let kick = Hitbox::new(
+            Vector2::new(45.0, 60.0),
+            Vector2::new(55.0, 50.0),
+            AttackData::new(10.0, 550.0, 45.0, 0.35).with_priority(1),
+        );
+        let low_kick = Hitbox::new(
+            Vector2::new(50.0, 20.0),
+            Vector2::new(75.0, 30.0),
+            AttackData::new(6.0, 350.0, 15.0, 0.25),
+        );
+
+        Self {
+            ground: AttackMove::new(
+                Player::FPS_ATK,
+                2,
+                2,
+                3,
+                FrameHitboxes::new(vec![vec![punch], vec![punch]]),
+            ),
+            air: AttackMove::new(
+                Player::FPS_AIR_ATK,
+                2,
+                2,
+                1,
+                FrameHitboxes::new(vec![vec![kick], vec![kick]]),
+            ),
+            crouch: AttackMove::new(
+                Player::FPS_CR_ATK,
+                1,
+                1,
+                2,
+                FrameHitboxes::new(vec![vec![low_kick]]),
+            ),
+        }
+    }
+}
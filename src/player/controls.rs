@@ -1,21 +1,108 @@
-use raylib::prelude::{KeyboardKey, MouseButton};
+use raylib::prelude::{GamepadAxis, GamepadButton, KeyboardKey, MouseButton, RaylibHandle};
+
+/// Stick tilt counted as a held direction
+const STICK_DEADZONE: f32 = 0.5;
+
+/// Key or mouse button a control is bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Binding {
+    Key(KeyboardKey),
+    Mouse(MouseButton),
+}
+
+impl Binding {
+    pub fn is_down(&self, raylib: &RaylibHandle) -> bool {
+        match *self {
+            Binding::Key(key) => raylib.is_key_down(key),
+            Binding::Mouse(button) => raylib.is_mouse_button_down(button),
+        }
+    }
+}
+
+/// Controls held this step, from the keyboard & gamepad combined
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Input {
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+    pub attack: bool,
+}
 
 pub struct Controls {
     pub up: KeyboardKey,
     pub down: KeyboardKey,
     pub left: KeyboardKey,
     pub right: KeyboardKey,
-    pub attack: MouseButton,
+    pub attack: Binding,
+    /// Gamepad also controlling the player, by index
+    pub gamepad: Option<i32>,
 }
 
 impl Default for Controls {
     fn default() -> Self {
+        Controls::wasd()
+    }
+}
+
+impl Controls {
+    /// WASD to move, left click to attack
+    pub fn wasd() -> Self {
         Controls {
             up: KeyboardKey::KEY_W,
             down: KeyboardKey::KEY_S,
             left: KeyboardKey::KEY_A,
             right: KeyboardKey::KEY_D,
-            attack: MouseButton::MOUSE_LEFT_BUTTON,
+            attack: Binding::Mouse(MouseButton::MOUSE_LEFT_BUTTON),
+            gamepad: None,
+        }
+    }
+
+    /// Arrow keys to move, right control to attack
+    pub fn arrows() -> Self {
+        Controls {
+            up: KeyboardKey::KEY_UP,
+            down: KeyboardKey::KEY_DOWN,
+            left: KeyboardKey::KEY_LEFT,
+            right: KeyboardKey::KEY_RIGHT,
+            attack: Binding::Key(KeyboardKey::KEY_RIGHT_CONTROL),
+            gamepad: None,
         }
     }
+
+    pub fn with_gamepad(mut self, gamepad: i32) -> Self {
+        self.gamepad = Some(gamepad);
+        self
+    }
+
+    /// Read the bound keys & gamepad (d-pad or left stick to move,
+    /// bottom face button to jump, left face button to attack)
+    pub fn read(&self, raylib: &RaylibHandle) -> Input {
+        let mut input = Input {
+            up: raylib.is_key_down(self.up),
+            down: raylib.is_key_down(self.down),
+            left: raylib.is_key_down(self.left),
+            right: raylib.is_key_down(self.right),
+            attack: self.attack.is_down(raylib),
+        };
+
+        let Some(gamepad) = self.gamepad.filter(|&id| raylib.is_gamepad_available(id)) else {
+            return input;
+        };
+        let button = |button| raylib.is_gamepad_button_down(gamepad, button);
+        let stick_x = raylib.get_gamepad_axis_movement(gamepad, GamepadAxis::GAMEPAD_AXIS_LEFT_X);
+        let stick_y = raylib.get_gamepad_axis_movement(gamepad, GamepadAxis::GAMEPAD_AXIS_LEFT_Y);
+
+        input.up |= button(GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_UP)
+            || button(GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_DOWN)
+            || stick_y < -STICK_DEADZONE;
+        input.down |=
+            button(GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_DOWN) || stick_y > STICK_DEADZONE;
+        input.left |=
+            button(GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_LEFT) || stick_x < -STICK_DEADZONE;
+        input.right |=
+            button(GamepadButton::GAMEPAD_BUTTON_LEFT_FACE_RIGHT) || stick_x > STICK_DEADZONE;
+        input.attack |= button(GamepadButton::GAMEPAD_BUTTON_RIGHT_FACE_LEFT);
+        input
+    }
 }
@@ -1,16 +1,24 @@
+use crate::{
+    combat::{ActiveHitbox, AttackPhase, HitEvent, Hitbox},
+    layers,
+};
 use rayexlib::{
     prelude::{
-        AnimationMachineBuilder, AnimationPlayer2D, ColliderInfo, FrameLimiter, Init, Ray2D, Rect2D,
+        overlaps_any, swept_bounds, AnimationMachineBuilder, AnimationPlayer2D, Bitmask2D,
+        ColliderInfo, CollisionObject, Init, KinematicBody2D, Ray2D, Rect2D,
     },
     state_manager::StateManager,
     traits::Renderable,
 };
-use raylib::prelude::{RaylibDraw, RaylibHandle, RaylibThread, Vector2};
+use raylib::prelude::{Color, RaylibDraw, RaylibHandle, RaylibThread, Rectangle, Vector2};
 
+mod attacks;
 mod controls;
 mod states;
+mod stats;
 
-use controls::Controls;
+use attacks::Attacks;
+pub use controls::{Controls, Input};
 pub use states::PlayerState;
 
 impl Player {
@@ -25,6 +33,11 @@ impl Player {
     pub const FPS_CRWK: f32 = 3.0;
     pub const FPS_DIVE: f32 = 20.0;
     pub const FPS_WSLD: f32 = 10.0;
+    pub const FPS_ATK: f32 = 14.0;
+    pub const FPS_AIR_ATK: f32 = 12.0;
+    pub const FPS_CR_ATK: f32 = 12.0;
+    pub const FPS_HURT: f32 = 10.0;
+    pub const FPS_DEATH: f32 = 10.0;
 
     // sprite & collision sizes
     pub const COLLISION_SIZE: Vector2 = Vector2::new(75.0, 106.0);
@@ -34,6 +47,29 @@ impl Player {
     pub const SPRITE_SL_SHIFT: f32 = 12.0;
     pub const SPRITE_SCALE: f32 = 3.5;
     pub const SPRITE_SIZE: Vector2 = Vector2::new(50.0, 37.0);
+    pub const SPRITE_ALPHA_THRESHOLD: u8 = 127;
+    pub const HURT_SIZE: Vector2 = Vector2::new(48.0, 48.0);
+    pub const DEATH_SIZE: Vector2 = Vector2::new(64.0, 64.0);
+
+    // knockback
+    /// Extra knockback per point of damage
+    pub const KNOCKBACK_DAMAGE: f32 = 0.05;
+    /// Extra knockback at no health left, less the healthier the player
+    pub const KNOCKBACK_HEALTH: f32 = 1.0;
+    /// Speed kept bouncing off a wall while launched
+    pub const WALL_BOUNCE: f32 = 0.6;
+    /// Slowest launch that still bounces off walls
+    pub const WALL_BOUNCE_MIN: f32 = 200.0;
+
+    // knock outs
+    /// Seconds knocked out before respawning
+    pub const RESPAWN_DELAY: f32 = 2.0;
+    /// Seconds held at the spawn point unless moving
+    pub const RESPAWN_HOLD: f32 = 1.0;
+    /// Seconds safe from hits after respawning
+    pub const INVULNERABLE_TIME: f32 = 2.0;
+    /// Blinks per second while invulnerable
+    pub const BLINK_RATE: f32 = 10.0;
 }
 
 pub struct Player {
@@ -47,6 +83,8 @@ pub struct Player {
     pub stamina: f32,
     pub endurance: f32,
     pub recovery: f32,
+    /// Out of stamina, can't run or attack until it recovers
+    pub exhausted: bool,
 
     // movement
     pub move_dir: Vector2,
@@ -54,12 +92,36 @@ pub struct Player {
     pub deceleration: f32,
     pub max_speed: f32,
     pub slide_speed: f32,
+    pub slope_speed: f32,
     pub jump: f32,
     pub dive: f32,
 
+    // combat
+    /// 1 facing right, -1 facing left
+    pub facing: f32,
+    /// Seconds left unable to act after being hit
+    pub hitstun: f32,
+    /// Launch from the last hit, applied on the next update
+    pub knockback: Option<Vector2>,
+    /// Attack boxes out this step
+    pub hitboxes: Vec<ActiveHitbox>,
+    /// Targets the current attack already hit
+    pub hit_targets: Vec<usize>,
+    pub attacks: Attacks,
+    /// Seconds into the current attack
+    pub attack_time: f32,
+    /// Lost a clash, the rest of the attack puts out no boxes
+    pub attack_cancelled: bool,
+    /// Seconds left knocked out, or held at the spawn point
+    pub respawn_timer: f32,
+    /// Seconds left safe from hits
+    pub invulnerable: f32,
+
     // physics
-    pub collider: Rect2D,
+    pub body: KinematicBody2D,
     pub ground_ray: Ray2D,
+    /// Nothing above the crouched hitbox blocks standing up
+    pub can_stand: bool,
     pub frame_time: f32,
     pub gravity: f32,
     pub ground_friction: f32,
@@ -67,9 +129,15 @@ pub struct Player {
 
     // drawing
     pub animation_player: AnimationPlayer2D<PlayerState>,
+    /// Sprite tint telling players apart
+    pub color: Color,
 
     // states
     pub controls: Controls,
+    /// Controls held this step
+    pub input: Input,
+    /// Ignores the controls, outside of a fight
+    pub locked: bool,
     pub state: PlayerState,
 }
 
@@ -86,6 +154,7 @@ impl Init for Player {
             stamina: 100.0,
             recovery: 4.0,
             endurance: 10.0,
+            exhausted: false,
 
             // movement
             move_dir: Vector2::zero(),
@@ -93,14 +162,35 @@ impl Init for Player {
             deceleration: 8.0,
             max_speed: 500.0,
             slide_speed: 500.0,
+            slope_speed: 0.5,
             jump: 600.0,
             dive: 1.5,
 
+            // combat
+            facing: 1.0,
+            hitstun: 0.0,
+            knockback: None,
+            hitboxes: Vec::new(),
+            hit_targets: Vec::new(),
+            attacks: Attacks::default(),
+            attack_time: 0.0,
+            attack_cancelled: false,
+            respawn_timer: 0.0,
+            invulnerable: 0.0,
+
             // physics
-            collider: Rect2D::newv(Player::COLLISION_SIZE).with_position(100.0, -100.0),
+            body: KinematicBody2D::new(
+                Rect2D::newv(Player::COLLISION_SIZE)
+                    .with_position(100.0, -100.0)
+                    .with_layer(layers::PLAYER)
+                    .with_mask(layers::PLAYER_MASK),
+            )
+            .with_rounded(true),
             ground_ray: Ray2D::new()
                 .with_position(200.0, 100.0)
-                .with_direction(Ray2D::DOWN * 50.0),
+                .with_direction(Ray2D::DOWN * 50.0)
+                .with_mask(layers::TERRAIN),
+            can_stand: true,
             frame_time: 0.0,
             gravity: 1500.0,
             ground_friction: 1.0,
@@ -108,7 +198,8 @@ impl Init for Player {
 
             // drawing
             animation_player: {
-                use crate::paths::player::advn;
+                use crate::paths::player::{advn, basic};
+                let attacks = Attacks::default();
 
                 // add animations
                 let mut b = AnimationMachineBuilder::new(Player::SPRITE_SIZE);
@@ -120,9 +211,47 @@ impl Init for Player {
                 b.add_animation(PlayerState::CrouchWalking, advn::CRWK, 6, Player::FPS_CRWK);
                 b.add_animation(PlayerState::Diving, advn::FALL, 2, Player::FPS_DIVE);
                 b.add_animation(PlayerState::WallSliding, advn::WSLD, 2, Player::FPS_WSLD);
+                b.add_sheet_animation(
+                    PlayerState::Attacking,
+                    advn::HAND_COMBAT,
+                    Attacks::GROUND_CELL,
+                    attacks.ground.frames(),
+                    Player::FPS_ATK,
+                );
+                b.add_sheet_animation(
+                    PlayerState::AirAttacking,
+                    advn::HAND_COMBAT,
+                    Attacks::AIR_CELL,
+                    attacks.air.frames(),
+                    Player::FPS_AIR_ATK,
+                );
+                b.add_sheet_animation(
+                    PlayerState::CrouchAttacking,
+                    advn::HAND_COMBAT,
+                    Attacks::CROUCH_CELL,
+                    attacks.crouch.frames(),
+                    Player::FPS_CR_ATK,
+                );
+                b.add_sized_animation(
+                    PlayerState::Hurt,
+                    basic::HURT,
+                    Player::HURT_SIZE,
+                    4,
+                    Player::FPS_HURT,
+                );
+                b.add_animation(PlayerState::Respawning, advn::IDLE, 4, Player::FPS_IDLE);
+                b.add_sized_animation(
+                    PlayerState::Dead,
+                    basic::DEATH,
+                    Player::DEATH_SIZE,
+                    10,
+                    Player::FPS_DEATH,
+                );
+                b.add_masks(Player::SPRITE_ALPHA_THRESHOLD);
 
                 // build animation player
                 let mut ap = b.build(raylib, thread);
+                ap.set_looping(&PlayerState::Dead, false);
 
                 // resize all animations
                 ap.set_scale(Player::SPRITE_SCALE);
@@ -130,55 +259,116 @@ impl Init for Player {
                 ap
             },
 
+            color: Color::WHITE,
+
             // states
             controls: Controls::default(),
+            input: Input::default(),
+            locked: false,
             state: PlayerState::default(),
         }
     }
 }
 
+impl Player {
+    pub fn with_controls(mut self, controls: Controls) -> Self {
+        self.controls = controls;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Start at `position`, in the air
+    pub fn with_position(mut self, x: f32, y: f32) -> Self {
+        self.body.collider.position = Vector2::new(x, y);
+        self.body.reset_interpolation();
+        self
+    }
+}
+
 impl Renderable for Player {
     fn update(&mut self, raylib: &mut RaylibHandle) {
         // calculate move direction
+        self.input = if self.locked {
+            Input::default()
+        } else {
+            self.controls.read(raylib)
+        };
         self.move_dir = Vector2 {
-            x: (raylib.is_key_down(self.controls.right) as i8
-                - raylib.is_key_down(self.controls.left) as i8) as f32,
-            y: (raylib.is_key_down(self.controls.down) as i8
-                - raylib.is_key_down(self.controls.up) as i8) as f32,
+            x: (self.input.right as i8 - self.input.left as i8) as f32,
+            y: (self.input.down as i8 - self.input.up as i8) as f32,
         };
 
-        // get frame time
-        self.frame_time = raylib.get_frame_time_limited();
+        // no control while stunned
+        if self.hitstun > 0.0 {
+            self.hitstun = (self.hitstun - self.frame_time).max(0.0);
+            self.move_dir = Vector2::zero();
+        }
+
+        // or knocked out
+        if self.state == PlayerState::Dead {
+            self.move_dir = Vector2::zero();
+        }
+
+        // blink while invulnerable
+        self.invulnerable = (self.invulnerable - self.frame_time).max(0.0);
+        let blink =
+            self.invulnerable > 0.0 && (self.invulnerable * Player::BLINK_RATE) as i32 % 2 == 0;
+        self.animation_player.set_tint(if blink {
+            self.color.fade(0.4)
+        } else {
+            self.color
+        });
 
-        // face direction
-        self.animation_player.face_x(self.move_dir.x);
+        // face direction, attacks can't turn around
+        if !self.state.is_attack() {
+            if self.move_dir.x != 0.0 {
+                self.facing = self.move_dir.x;
+            }
+            self.animation_player.face_x(self.move_dir.x);
+        }
 
-        // reset x velocity on wall collision
-        if self.collider.on_wall() {
-            self.collider.velocity.x = 0.0;
+        // reset x velocity on wall collision, launched players bounce off instead
+        if self.body.on_wall() && self.state != PlayerState::Hurt {
+            self.body.collider.velocity.x = 0.0;
         }
 
         // gravity
-        self.collider.velocity.y = if self.collider.on_floor() {
+        self.body.collider.velocity.y = if self.body.on_floor() {
             0.0 // reset y velocity on floor
         } else {
             // add gravity force
-            self.collider.velocity.y + self.gravity * self.frame_time
+            self.body.collider.velocity.y + self.gravity * self.frame_time
         };
 
+        // launched by a hit, after gravity so the floor doesn't cancel it
+        if let Some(launch) = self.knockback.take() {
+            self.body.collider.velocity = launch;
+            StateManager::next_state(self, PlayerState::Hurt, raylib);
+        }
+
         // current state update
         StateManager::update(self, raylib);
 
+        // stamina & health
+        self.update_stats();
+
         // terminal velocity
         let terminal_vel = self.gravity * 2.0;
-        self.collider.velocity = self.collider.velocity.clamp(-terminal_vel, terminal_vel);
-
-        // move player with velocity
-        self.collider.position += self.collider.velocity * self.frame_time;
+        self.body.collider.velocity = self
+            .body
+            .collider
+            .velocity
+            .clamp(-terminal_vel, terminal_vel);
 
         // update animation
-        self.animation_player.set_position(self.collider.position);
-        self.animation_player.next_frame(&self.state);
+        self.animation_player
+            .set_position(self.body.collider.position);
+        self.animation_player
+            .next_frame(&self.state, self.frame_time);
     }
 
     fn draw(&self, raylib: &mut impl RaylibDraw) {
@@ -189,11 +379,26 @@ impl Renderable for Player {
 
 impl ColliderInfo for Player {
     fn center(&self) -> Vector2 {
-        self.collider.center()
+        self.body.center()
     }
 
     fn velocity(&self) -> Vector2 {
-        self.collider.velocity()
+        self.body.velocity()
+    }
+}
+
+impl Player {
+    /// Pixel perfect hurtbox of the current animation frame
+    pub fn sprite_mask(&self) -> Option<Bitmask2D> {
+        self.animation_player
+            .bitmask(&self.state)
+            .map(|mask| mask.with_layer(layers::HURTBOX))
+    }
+
+    /// Draw between the last two physics steps
+    pub fn draw_interpolated(&self, alpha: f32, raylib: &mut impl RaylibDraw) {
+        let position = self.body.interpolated_position(alpha);
+        self.animation_player.draw_at(&self.state, position, raylib);
     }
 }
 
@@ -201,35 +406,200 @@ impl ColliderInfo for Player {
 impl Player {
     pub fn reset_hitbox_from_crouch(&mut self) {
         // move hitbox by offset of sizes
-        self.collider.position.y -= Player::COLLISION_SIZE.y - Player::CROUCH_SIZE;
+        self.body.collider.position.y -= Player::COLLISION_SIZE.y - Player::CROUCH_SIZE;
         // reset hitbox size
-        self.collider.size = Player::COLLISION_SIZE;
+        self.body.collider.size = Player::COLLISION_SIZE;
+    }
+
+    /// Move player to `position` at rest, in the air
+    pub fn teleport(&mut self, position: Vector2, raylib: &mut RaylibHandle) {
+        if self.body.collider.size != Player::COLLISION_SIZE {
+            self.reset_hitbox_from_crouch();
+        }
+
+        self.body.collider.position = position;
+        self.body.collider.velocity = Vector2::zero();
+        self.body.reset_interpolation();
+        self.reset_colliding();
+        StateManager::next_state(self, PlayerState::Falling, raylib);
+    }
+
+    /// Body box attacks can hit
+    pub fn hurtbox(&self) -> Rect2D {
+        let collider = &self.body.collider;
+        Rect2D::newv(collider.size)
+            .with_position(collider.position.x, collider.position.y)
+            .with_layer(layers::HURTBOX)
+    }
+
+    /// Place `hitboxes` around the player, facing the player's way
+    pub fn set_hitboxes(&mut self, hitboxes: &[Hitbox]) {
+        let collider = &self.body.collider;
+        self.hitboxes = hitboxes
+            .iter()
+            .map(|hitbox| ActiveHitbox {
+                rect: hitbox.placed(collider.position, collider.size.x, self.facing),
+                data: hitbox.data,
+            })
+            .collect();
+    }
+
+    /// Restart the current state's attack
+    pub fn start_attack(&mut self) {
+        self.attack_time = 0.0;
+        self.attack_cancelled = false;
+        self.hitboxes.clear();
+        self.hit_targets.clear();
+        self.animation_player.reset_frame(&self.state);
+    }
+
+    /// Place the current attack's boxes & advance it, none once it's over
+    pub fn update_attack(&mut self) -> Option<AttackPhase> {
+        let attack = self.attacks.get(&self.state)?;
+        let phase = attack.phase(self.attack_time);
+        let hitboxes = if self.attack_cancelled {
+            Vec::new()
+        } else {
+            attack.hitboxes(self.attack_time).to_vec()
+        };
+
+        self.set_hitboxes(&hitboxes);
+        self.attack_time += self.frame_time;
+        phase
+    }
+
+    /// Pull the current attack's boxes back in
+    pub fn end_attack(&mut self) {
+        self.hitboxes.clear();
+        self.hit_targets.clear();
+        self.attack_cancelled = false;
+    }
+
+    /// Stop the current attack after losing a clash, recovering as usual
+    pub fn cancel_attack(&mut self) {
+        self.hitboxes.clear();
+        self.attack_cancelled = true;
+    }
+
+    /// Safe from hits, while down or just respawned
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable > 0.0 || matches!(self.state, PlayerState::Dead | PlayerState::Respawning)
+    }
+
+    /// Out of health or outside `blast_zone`, & not already down
+    pub fn is_knocked_out(&self, blast_zone: &Rectangle) -> bool {
+        let center = self.center();
+        let outside = center.x < blast_zone.x
+            || center.y < blast_zone.y
+            || center.x > blast_zone.x + blast_zone.width
+            || center.y > blast_zone.y + blast_zone.height;
+        self.state != PlayerState::Dead && (self.health <= 0.0 || outside)
+    }
+
+    pub fn knock_out(&mut self, raylib: &mut RaylibHandle) {
+        if self.state != PlayerState::Dead {
+            StateManager::next_state(self, PlayerState::Dead, raylib);
+        }
+    }
+
+    /// Knocked out long enough to come back
+    pub fn can_respawn(&self) -> bool {
+        self.state == PlayerState::Dead && self.respawn_timer <= 0.0
+    }
+
+    /// Back at `position` with full stats, safe from hits for a while
+    pub fn respawn(&mut self, position: Vector2, raylib: &mut RaylibHandle) {
+        self.reset_stats();
+        self.teleport(position, raylib);
+        self.invulnerable = Player::INVULNERABLE_TIME;
+        StateManager::next_state(self, PlayerState::Respawning, raylib);
+    }
+
+    /// Back at `position` with full stats for a new match
+    pub fn reset(&mut self, position: Vector2, raylib: &mut RaylibHandle) {
+        self.reset_stats();
+        self.invulnerable = 0.0;
+        self.teleport(position, raylib);
+    }
+
+    /// Take damage, stun & knockback from a landed hit
+    /// (launched further by heavy hits & the lower the health left)
+    pub fn take_hit(&mut self, hit: &HitEvent<usize>) {
+        self.health = (self.health - hit.data.damage).max(0.0);
+        self.hitstun = hit.data.hitstun;
+
+        let damage_scale = 1.0 + hit.data.damage * Player::KNOCKBACK_DAMAGE;
+        let health_scale = 1.0 + (1.0 - self.health / self.max_health) * Player::KNOCKBACK_HEALTH;
+        self.knockback = Some(hit.launch() * damage_scale * health_scale);
     }
 
     pub fn reset_colliding(&mut self) {
         // reset player collisions
-        self.collider.reset_colliding();
+        self.body.reset_colliding();
         self.ground_ray.reset_colliding();
     }
 
-    pub fn collide_rects(&mut self, raylib: &mut RaylibHandle, floors: &mut Vec<Rect2D>) {
-        // collide player & floors
-        for floor in floors {
-            floor.reset_colliding();
+    /// Speed multiplier for moving along the floor in `dir_x`
+    /// (above 1 downhill, below 1 uphill, 1 on flat ground)
+    pub fn slope_factor(&self, dir_x: f32) -> f32 {
+        let normal = self.body.floor_normal().unwrap_or(KinematicBody2D::UP);
+        1.0 + normal.x * dir_x * self.slope_speed
+    }
 
-            if self.collider.collide_rect(floor)
-                && self.collider.on_floor()
-                && self.move_dir.y == 1.0
-            {
-                // update ray position
-                self.ground_ray.set_position(
-                    self.collider.position.x + (Player::CROUCH_SIZE / 2.0),
-                    self.collider.position.y + self.collider.size.y,
-                );
+    /// Area the player can reach this frame, for broad phase queries
+    pub fn motion_bounds(&self) -> Rectangle {
+        let motion = (self.body.collider.velocity + self.body.floor_velocity()) * self.frame_time;
+        swept_bounds(self.body.collider.bounds(), motion)
+    }
+
+    /// Move & slide player along nearby terrain (see `motion_bounds`)
+    pub fn collide_terrain(&mut self, raylib: &mut RaylibHandle, terrain: &[&dyn CollisionObject]) {
+        let velocity = self.body.collider.velocity;
+        self.body.move_and_slide(velocity, self.frame_time, terrain);
+
+        // launched players bounce off walls they fly into
+        let into_wall = (self.body.on_wall_left() && velocity.x < 0.0)
+            || (self.body.on_wall_right() && velocity.x > 0.0);
+        if self.state == PlayerState::Hurt
+            && into_wall
+            && velocity.x.abs() > Player::WALL_BOUNCE_MIN
+        {
+            self.body.collider.velocity.x = -velocity.x * Player::WALL_BOUNCE;
+        }
 
-                // check ray collision
-                self.ground_ray.check_rect(floor);
+        // check for ground in front of player
+        self.ground_ray.reset_colliding();
+        if self.body.on_floor() && self.move_dir.y == 1.0 {
+            // update ray position
+            self.ground_ray.set_position(
+                self.body.collider.position.x + (Player::CROUCH_SIZE / 2.0),
+                self.body.collider.position.y + self.body.collider.size.y,
+            );
+
+            // check ray collision
+            for other in terrain {
+                self.ground_ray.check(*other);
             }
         }
+
+        self.can_stand = self.stand_clearance(terrain);
+    }
+
+    /// Space above the crouched hitbox is free of solid terrain
+    /// (one way colliders are stood up through)
+    fn stand_clearance(&self, terrain: &[&dyn CollisionObject]) -> bool {
+        let collider = &self.body.collider;
+        let growth = Player::COLLISION_SIZE.y - collider.size.y;
+        if growth <= 0.0 {
+            return true;
+        }
+
+        // inset so walls beside the player don't count
+        let head_room = Rect2D::new(collider.size.x - 2.0, growth)
+            .with_position(collider.position.x + 1.0, collider.position.y - growth)
+            .with_mask(collider.mask);
+
+        let solid = terrain.iter().copied().filter(|other| !other.one_way());
+        !overlaps_any(&head_room, solid)
     }
 }
@@ -0,0 +1,43 @@
+use super::*;
+
+pub fn on_enter(player: &mut Player, _raylib: &mut RaylibHandle) {
+    player.start_attack();
+}
+
+pub fn on_exit(player: &mut Player, _raylib: &mut RaylibHandle) {
+    player.end_attack();
+}
+
+pub fn update(player: &mut Player, raylib: &mut RaylibHandle) {
+    if player.move_dir.x == 0.0 {
+        // stop velocity
+        player.body.collider.velocity.x.lerp(
+            0.0,
+            player.air_friction * player.deceleration * player.frame_time,
+        );
+        // round small values to 0
+        player.body.collider.velocity.x.round_zero();
+    } else {
+        // drift towards max speed
+        player.body.collider.velocity.x.lerp(
+            player.move_dir.x * player.max_speed,
+            player.acceleration * player.frame_time,
+        );
+    }
+
+    // landing cancels the attack
+    if player.body.on_floor() || player.update_attack().is_none() {
+        check_next_state(player, raylib);
+    }
+}
+
+#[inline]
+fn check_next_state(player: &mut Player, raylib: &mut RaylibHandle) {
+    if !player.body.on_floor() {
+        StateManager::next_state(player, PlayerState::Falling, raylib);
+    } else if player.move_dir.x == 0.0 {
+        StateManager::next_state(player, PlayerState::Idle, raylib);
+    } else {
+        StateManager::next_state(player, PlayerState::Running, raylib);
+    }
+}
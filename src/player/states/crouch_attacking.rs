@@ -0,0 +1,50 @@
+use super::*;
+
+pub fn on_enter(player: &mut Player, raylib: &mut RaylibHandle) {
+    // crouched hitbox & sprite offset
+    crouching::on_enter(player, raylib);
+    player.start_attack();
+}
+
+pub fn on_exit(player: &mut Player, raylib: &mut RaylibHandle) {
+    player.end_attack();
+    crouching::on_exit(player, raylib);
+}
+
+pub fn update(player: &mut Player, raylib: &mut RaylibHandle) {
+    // calculate friction damping
+    let g_friction = player.ground_friction * player.deceleration * player.frame_time;
+
+    // stop velocity
+    player.body.collider.velocity.x.lerp(0.0, g_friction);
+
+    // round small values to 0
+    player.body.collider.velocity.x.round_zero();
+
+    // next state once the attack is over
+    if player.update_attack().is_none() {
+        check_next_state(player, raylib);
+    }
+}
+
+#[inline]
+fn check_next_state(player: &mut Player, raylib: &mut RaylibHandle) {
+    if !player.body.on_floor() {
+        player.reset_hitbox_from_crouch();
+        StateManager::next_state(player, PlayerState::Falling, raylib);
+    } else if player.input.down || !player.can_stand {
+        // stay crouched under low ceilings
+        if player.move_dir.x == 0.0 {
+            StateManager::next_state(player, PlayerState::Crouching, raylib);
+        } else {
+            StateManager::next_state(player, PlayerState::CrouchWalking, raylib);
+        }
+    } else {
+        player.reset_hitbox_from_crouch();
+        if player.move_dir.x == 0.0 {
+            StateManager::next_state(player, PlayerState::Idle, raylib);
+        } else {
+            StateManager::next_state(player, PlayerState::Running, raylib);
+        }
+    }
+}
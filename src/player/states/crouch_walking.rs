@@ -12,16 +12,17 @@ pub fn on_exit(player: &mut Player, _raylib: &mut RaylibHandle) {
 
 pub fn update(player: &mut Player, raylib: &mut RaylibHandle) {
     // accelerate velocity to crouch walk speed
-    player.collider.velocity.x.lerp(
-        player.move_dir.x * (player.max_speed / 4.0),
+    let max_speed = player.max_speed / 4.0 * player.slope_factor(player.move_dir.x);
+    player.body.collider.velocity.x.lerp(
+        player.move_dir.x * max_speed,
         player.acceleration * player.frame_time,
     );
 
     // player ledge to wall slide transition
-    if player.collider.on_floor() && player.move_dir.y == 1.0 && !player.ground_ray.is_colliding() {
+    if player.body.on_floor() && player.move_dir.y == 1.0 && !player.ground_ray.is_colliding() {
         // move collider, force collision resolution to wall
-        player.collider.position.y += Player::CROUCH_SIZE / 2.0;
-        player.collider.reset_colliding();
+        player.body.collider.position.y += Player::CROUCH_SIZE / 2.0;
+        player.body.reset_colliding();
         player.reset_hitbox_from_crouch();
 
         // self.transition(PlayerState::WallSliding, raylib);
@@ -34,15 +35,24 @@ pub fn update(player: &mut Player, raylib: &mut RaylibHandle) {
 
 #[inline]
 fn check_next_state(player: &mut Player, raylib: &mut raylib::RaylibHandle) {
-    if player.move_dir.x == 0.0 {
+    if player.wants_attack() && player.body.on_floor() {
+        StateManager::next_state(player, PlayerState::CrouchAttacking, raylib);
+    } else if player.move_dir.x == 0.0 {
         StateManager::next_state(player, PlayerState::Crouching, raylib);
-    } else if !raylib.is_key_down(player.controls.down) {
+    } else if !player.can_stand {
+        // stay crouched under low ceilings
+    } else if !player.input.down {
         player.reset_hitbox_from_crouch();
         StateManager::next_state(player, PlayerState::Running, raylib);
-    } else if raylib.is_key_down(player.controls.up) {
+    } else if player.input.up && player.body.on_one_way_floor() {
+        // drop through one way platform
+        player.reset_hitbox_from_crouch();
+        player.body.drop_through();
+        StateManager::next_state(player, PlayerState::Falling, raylib);
+    } else if player.input.up {
         player.reset_hitbox_from_crouch();
         StateManager::next_state(player, PlayerState::Jumping, raylib);
-    } else if player.collider.on_wall() && !player.collider.on_floor() {
+    } else if player.body.on_wall() && !player.body.on_floor() {
         player.reset_hitbox_from_crouch();
         StateManager::next_state(player, PlayerState::WallSliding, raylib);
     }
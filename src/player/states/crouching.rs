@@ -1,11 +1,11 @@
 use super::*;
 
 pub fn on_enter(player: &mut Player, _raylib: &mut RaylibHandle) {
-    if player.collider.size.y != Player::CROUCH_SIZE {
+    if player.body.collider.size.y != Player::CROUCH_SIZE {
         // change hitbox height
-        player.collider.size.y = Player::CROUCH_SIZE;
+        player.body.collider.size.y = Player::CROUCH_SIZE;
         // move hitbox by offset of sizes
-        player.collider.position.y += Player::COLLISION_SIZE.y - Player::CROUCH_SIZE;
+        player.body.collider.position.y += Player::COLLISION_SIZE.y - Player::CROUCH_SIZE;
     }
 
     // set sprite to crouch offset
@@ -20,17 +20,23 @@ pub fn on_exit(player: &mut Player, _raylib: &mut raylib::prelude::RaylibHandle)
 pub fn update(player: &mut Player, raylib: &mut raylib::prelude::RaylibHandle) {
     // calculate friction damping
     let g_friction = player.ground_friction * player.deceleration * player.frame_time;
-    // stop velocity
-    player.collider.velocity.x.lerp(0.0, g_friction);
+    // slide down slopes, faster the steeper they are (stop on flat ground)
+    let slope = player.body.floor_normal().map_or(0.0, |normal| normal.x);
+    player
+        .body
+        .collider
+        .velocity
+        .x
+        .lerp(slope * player.slide_speed, g_friction);
     // round small values to 0
-    player.collider.velocity.x.round_zero();
+    player.body.collider.velocity.x.round_zero();
 
     // player ledge to wall slide transition
-    if player.collider.on_floor() && player.move_dir.y == 1.0 && !player.ground_ray.is_colliding() {
+    if player.body.on_floor() && player.move_dir.y == 1.0 && !player.ground_ray.is_colliding() {
         // move collider, force collision resolution to wall
-        player.collider.position.y += Player::CROUCH_SIZE / 2.0;
-        player.collider.position.x += Player::COLLISION_SIZE.x * player.move_dir.x;
-        player.collider.reset_colliding();
+        player.body.collider.position.y += Player::CROUCH_SIZE / 2.0;
+        player.body.collider.position.x += Player::COLLISION_SIZE.x * player.move_dir.x;
+        player.body.reset_colliding();
         player.reset_hitbox_from_crouch();
 
         // self.transition(PlayerState::WallSliding, raylib);
@@ -43,15 +49,29 @@ pub fn update(player: &mut Player, raylib: &mut raylib::prelude::RaylibHandle) {
 
 #[inline]
 fn check_next_state(player: &mut Player, raylib: &mut raylib::RaylibHandle) {
-    if player.move_dir.x != 0.0 {
+    if player.wants_attack() && player.body.on_floor() {
+        StateManager::next_state(player, PlayerState::CrouchAttacking, raylib);
+    } else if player.move_dir.x != 0.0 {
         StateManager::next_state(player, PlayerState::CrouchWalking, raylib);
-    } else if !raylib.is_key_down(player.controls.down) {
+    } else if player.input.down && player.input.up && player.body.on_one_way_floor() {
+        // drop through one way platform, even under a low ceiling
+        if player.can_stand {
+            player.reset_hitbox_from_crouch();
+        } else {
+            // grow down through the platform instead of up into the ceiling
+            player.body.collider.size = Player::COLLISION_SIZE;
+        }
+        player.body.drop_through();
+        StateManager::next_state(player, PlayerState::Falling, raylib);
+    } else if !player.can_stand {
+        // stay crouched under low ceilings
+    } else if !player.input.down {
         player.reset_hitbox_from_crouch();
         StateManager::next_state(player, PlayerState::Idle, raylib);
-    } else if raylib.is_key_down(player.controls.up) {
+    } else if player.input.up {
         player.reset_hitbox_from_crouch();
         StateManager::next_state(player, PlayerState::Jumping, raylib);
-    } else if player.collider.on_wall() && !player.collider.on_floor() {
+    } else if player.body.on_wall() && !player.body.on_floor() {
         player.reset_hitbox_from_crouch();
         StateManager::next_state(player, PlayerState::WallSliding, raylib);
     }
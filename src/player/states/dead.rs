@@ -0,0 +1,40 @@
+use super::*;
+
+pub fn on_enter(player: &mut Player, _raylib: &mut RaylibHandle) {
+    // fall down at full size, unless under a low ceiling
+    if player.body.collider.size != Player::COLLISION_SIZE && player.can_stand {
+        player.reset_hitbox_from_crouch();
+    }
+
+    // fly off with the knock out hit, no stun left to wait out
+    if let Some(launch) = player.knockback.take() {
+        player.body.collider.velocity = launch;
+    }
+    player.hitstun = 0.0;
+    player.end_attack();
+
+    // wait, then respawn
+    player.respawn_timer = Player::RESPAWN_DELAY;
+    player.animation_player.reset_frame(&PlayerState::Dead);
+}
+
+pub fn update(player: &mut Player, _raylib: &mut RaylibHandle) {
+    // calculate friction damping
+    let friction = if player.body.on_floor() {
+        player.ground_friction
+    } else {
+        player.air_friction
+    };
+    player
+        .body
+        .collider
+        .velocity
+        .x
+        .lerp(0.0, friction * player.deceleration * player.frame_time);
+
+    // round small values to 0
+    player.body.collider.velocity.x.round_zero();
+
+    // respawned by the level once the timer runs out
+    player.respawn_timer = (player.respawn_timer - player.frame_time).max(0.0);
+}
@@ -3,22 +3,22 @@ use super::*;
 pub fn update(player: &mut Player, raylib: &mut raylib::prelude::RaylibHandle) {
     if player.move_dir.x == 0.0 {
         // stop velocity
-        player.collider.velocity.x.lerp(
+        player.body.collider.velocity.x.lerp(
             0.0,
             player.air_friction * player.deceleration * player.frame_time,
         );
         // round small values to 0
-        player.collider.velocity.x.round_zero();
+        player.body.collider.velocity.x.round_zero();
     } else {
         // accelerate velocity to max speed
-        player.collider.velocity.x.lerp(
+        player.body.collider.velocity.x.lerp(
             player.move_dir.x * player.max_speed,
             player.acceleration * player.frame_time,
         );
     }
 
     // add downward velocity
-    player.collider.velocity.y += player.gravity * player.dive * player.frame_time;
+    player.body.collider.velocity.y += player.gravity * player.dive * player.frame_time;
 
     // next state
     check_next_state(player, raylib);
@@ -26,9 +26,9 @@ pub fn update(player: &mut Player, raylib: &mut raylib::prelude::RaylibHandle) {
 
 #[inline]
 fn check_next_state(player: &mut Player, raylib: &mut raylib::RaylibHandle) {
-    let a = player.collider.on_floor();
+    let a = player.body.on_floor();
     let b = player.move_dir.x == 0.0;
-    let c = raylib.is_key_down(player.controls.down);
+    let c = player.input.down;
 
     match (a, b, c) {
         (true, true, _) => StateManager::next_state(player, PlayerState::Idle, raylib),
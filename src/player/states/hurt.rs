@@ -0,0 +1,54 @@
+use super::*;
+
+pub fn on_enter(player: &mut Player, _raylib: &mut RaylibHandle) {
+    // stand up to take the hit, unless under a low ceiling
+    if player.body.collider.size != Player::COLLISION_SIZE && player.can_stand {
+        player.reset_hitbox_from_crouch();
+    }
+
+    // reset hurt animation
+    player.animation_player.reset_frame(&PlayerState::Hurt);
+}
+
+pub fn update(player: &mut Player, raylib: &mut RaylibHandle) {
+    // launched players only slow down, by air or ground friction
+    let friction = if player.body.on_floor() {
+        player.ground_friction
+    } else {
+        player.air_friction
+    };
+    player
+        .body
+        .collider
+        .velocity
+        .x
+        .lerp(0.0, friction * player.deceleration * player.frame_time);
+
+    // round small values to 0
+    player.body.collider.velocity.x.round_zero();
+
+    // next state
+    check_next_state(player, raylib);
+}
+
+#[inline]
+fn check_next_state(player: &mut Player, raylib: &mut RaylibHandle) {
+    let crouched = player.body.collider.size != Player::COLLISION_SIZE;
+
+    if player.hitstun > 0.0 {
+        // stunned
+    } else if crouched && !player.can_stand {
+        // hit under a low ceiling, stay down once landed
+        if player.body.on_floor() {
+            StateManager::next_state(player, PlayerState::Crouching, raylib);
+        }
+    } else if crouched {
+        player.reset_hitbox_from_crouch();
+    } else if !player.body.on_floor() {
+        StateManager::next_state(player, PlayerState::Falling, raylib);
+    } else if player.move_dir.x == 0.0 {
+        StateManager::next_state(player, PlayerState::Idle, raylib);
+    } else {
+        StateManager::next_state(player, PlayerState::Running, raylib);
+    }
+}
@@ -7,10 +7,10 @@ pub fn update(player: &mut Player, raylib: &mut RaylibHandle) {
     let g_friction = player.ground_friction * player.deceleration * player.frame_time;
 
     // stop velocity
-    player.collider.velocity.x.lerp(0.0, g_friction);
+    player.body.collider.velocity.x.lerp(0.0, g_friction);
 
     // round small values to 0
-    player.collider.velocity.x.round_zero();
+    player.body.collider.velocity.x.round_zero();
 
     // update idle animation speed
     let fps_rate = math::lerp(
@@ -28,12 +28,14 @@ pub fn update(player: &mut Player, raylib: &mut RaylibHandle) {
 
 #[inline]
 fn check_next_state(player: &mut Player, raylib: &mut raylib::RaylibHandle) {
-    if player.collider.on_floor() {
-        if player.move_dir.x != 0.0 {
+    if player.body.on_floor() {
+        if player.wants_attack() {
+            StateManager::next_state(player, PlayerState::Attacking, raylib);
+        } else if player.move_dir.x != 0.0 {
             StateManager::next_state(player, PlayerState::Running, raylib);
-        } else if raylib.is_key_down(player.controls.up) {
+        } else if player.input.up {
             StateManager::next_state(player, PlayerState::Jumping, raylib);
-        } else if raylib.is_key_down(player.controls.down) {
+        } else if player.input.down {
             StateManager::next_state(player, PlayerState::Crouching, raylib);
         }
     } else {
@@ -3,14 +3,20 @@ use rayexlib::{
     prelude::{math, Math},
     state_manager::StateManager,
 };
-use raylib::prelude::RaylibHandle;
+use raylib::prelude::{RaylibHandle, Vector2};
 
+mod air_attacking;
+mod attacking;
+mod crouch_attacking;
 mod crouch_walking;
 mod crouching;
+mod dead;
 mod diving;
 mod falling;
+mod hurt;
 mod idle;
 mod jumping;
+mod respawning;
 mod running;
 mod wall_sliding;
 
@@ -25,6 +31,24 @@ pub enum PlayerState {
     Falling,
     WallSliding,
     Diving,
+    Attacking,
+    AirAttacking,
+    CrouchAttacking,
+    Hurt,
+    /// Knocked out, waiting to respawn
+    Dead,
+    /// Held at the spawn point until moving
+    Respawning,
+}
+
+impl PlayerState {
+    /// State plays an attack, the player can't turn around
+    pub fn is_attack(&self) -> bool {
+        matches!(
+            self,
+            PlayerState::Attacking | PlayerState::AirAttacking | PlayerState::CrouchAttacking
+        )
+    }
 }
 
 impl StateManager<PlayerState> for Player {
@@ -38,6 +62,12 @@ impl StateManager<PlayerState> for Player {
             PlayerState::Falling => falling::update(player, raylib),
             PlayerState::Diving => diving::update(player, raylib),
             PlayerState::WallSliding => wall_sliding::update(player, raylib),
+            PlayerState::Attacking => attacking::update(player, raylib),
+            PlayerState::AirAttacking => air_attacking::update(player, raylib),
+            PlayerState::CrouchAttacking => crouch_attacking::update(player, raylib),
+            PlayerState::Hurt => hurt::update(player, raylib),
+            PlayerState::Dead => dead::update(player, raylib),
+            PlayerState::Respawning => respawning::update(player, raylib),
         }
     }
 
@@ -51,6 +81,12 @@ impl StateManager<PlayerState> for Player {
             PlayerState::Falling => {}
             PlayerState::Diving => {}
             PlayerState::WallSliding => wall_sliding::on_enter(player, raylib),
+            PlayerState::Attacking => attacking::on_enter(player, raylib),
+            PlayerState::AirAttacking => air_attacking::on_enter(player, raylib),
+            PlayerState::CrouchAttacking => crouch_attacking::on_enter(player, raylib),
+            PlayerState::Hurt => hurt::on_enter(player, raylib),
+            PlayerState::Dead => dead::on_enter(player, raylib),
+            PlayerState::Respawning => respawning::on_enter(player, raylib),
         }
     }
 
@@ -64,6 +100,12 @@ impl StateManager<PlayerState> for Player {
             PlayerState::Falling => {}
             PlayerState::Diving => {}
             PlayerState::WallSliding => wall_sliding::on_exit(player, raylib),
+            PlayerState::Attacking => attacking::on_exit(player, raylib),
+            PlayerState::AirAttacking => air_attacking::on_exit(player, raylib),
+            PlayerState::CrouchAttacking => crouch_attacking::on_exit(player, raylib),
+            PlayerState::Hurt => {}
+            PlayerState::Dead => {}
+            PlayerState::Respawning => {}
         }
     }
 
@@ -0,0 +1,22 @@
+use super::*;
+
+pub fn on_enter(player: &mut Player, _raylib: &mut RaylibHandle) {
+    player.respawn_timer = Player::RESPAWN_HOLD;
+}
+
+pub fn update(player: &mut Player, raylib: &mut RaylibHandle) {
+    // float at the spawn point
+    player.body.collider.velocity = Vector2::zero();
+
+    player.respawn_timer = (player.respawn_timer - player.frame_time).max(0.0);
+
+    // next state
+    check_next_state(player, raylib);
+}
+
+#[inline]
+fn check_next_state(player: &mut Player, raylib: &mut RaylibHandle) {
+    if player.respawn_timer <= 0.0 || player.move_dir != Vector2::zero() {
+        StateManager::next_state(player, PlayerState::Falling, raylib);
+    }
+}
@@ -1,24 +1,35 @@
 use super::*;
 
 pub fn update(player: &mut Player, raylib: &mut raylib::prelude::RaylibHandle) {
-    // accelerate velocity to max speed
-    player.collider.velocity.x.lerp(
-        player.move_dir.x * player.max_speed,
+    // accelerate velocity to max speed, slower uphill & faster downhill
+    let max_speed = player.run_speed() * player.slope_factor(player.move_dir.x);
+    player.body.collider.velocity.x.lerp(
+        player.move_dir.x * max_speed,
         player.acceleration * player.frame_time,
     );
 
+    // walk while exhausted
+    let fps = if player.exhausted {
+        Player::FPS_WALK
+    } else {
+        Player::FPS_RUN
+    };
+    player.animation_player.set_fps(PlayerState::Running, fps);
+
     // next state
     check_next_state(player, raylib);
 }
 
 #[inline]
 fn check_next_state(player: &mut Player, raylib: &mut raylib::RaylibHandle) {
-    if player.collider.on_floor() {
-        if player.move_dir.x == 0.0 {
+    if player.body.on_floor() {
+        if player.wants_attack() {
+            StateManager::next_state(player, PlayerState::Attacking, raylib);
+        } else if player.move_dir.x == 0.0 {
             StateManager::next_state(player, PlayerState::Idle, raylib);
-        } else if raylib.is_key_down(player.controls.down) {
+        } else if player.input.down {
             StateManager::next_state(player, PlayerState::Crouching, raylib);
-        } else if raylib.is_key_down(player.controls.up) {
+        } else if player.input.up {
             StateManager::next_state(player, PlayerState::Jumping, raylib);
         }
     } else {
@@ -0,0 +1,81 @@
+use super::{Player, PlayerState};
+
+impl Player {
+    /// Stamina left, as a fraction of the maximum, to stop being exhausted
+    pub const EXHAUSTION_RECOVERY: f32 = 0.5;
+    /// Run speed multiplier while exhausted
+    pub const EXHAUSTED_SPEED: f32 = 0.5;
+}
+
+impl PlayerState {
+    /// Stamina drained per second, in multiples of `endurance`
+    fn stamina_drain(&self) -> f32 {
+        match self {
+            PlayerState::Running => 1.0,
+            PlayerState::Diving => 1.5,
+            PlayerState::WallSliding => 0.5,
+            PlayerState::Attacking | PlayerState::AirAttacking | PlayerState::CrouchAttacking => {
+                3.0
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// Player stamina & health functions
+impl Player {
+    /// Drain stamina in tiring states, recover it otherwise & regenerate health
+    pub fn update_stats(&mut self) {
+        // stamina
+        // nothing drains while exhausted so holding a direction still recovers
+        let drain = if self.exhausted {
+            0.0
+        } else {
+            self.state.stamina_drain()
+        };
+        let rate = if drain > 0.0 {
+            -drain * self.endurance
+        } else {
+            self.recovery
+        };
+        self.stamina = (self.stamina + rate * self.frame_time).clamp(0.0, self.max_stamina);
+
+        // exhaustion
+        if self.stamina == 0.0 {
+            self.exhausted = true;
+        } else if self.stamina >= self.max_stamina * Player::EXHAUSTION_RECOVERY {
+            self.exhausted = false;
+        }
+
+        // health, not while reeling from a hit
+        if self.health > 0.0 && self.state != PlayerState::Hurt {
+            self.health = (self.health + self.regeneration * self.frame_time).min(self.max_health);
+        }
+    }
+
+    /// Back to full health & stamina, dropping any hit or attack in progress
+    pub fn reset_stats(&mut self) {
+        self.health = self.max_health;
+        self.stamina = self.max_stamina;
+        self.exhausted = false;
+        self.hitstun = 0.0;
+        self.knockback = None;
+        self.attack_time = 0.0;
+        self.hitboxes.clear();
+        self.hit_targets.clear();
+    }
+
+    /// Attack input is held & the player has the stamina for it
+    pub fn wants_attack(&self) -> bool {
+        !self.exhausted && self.input.attack
+    }
+
+    /// Top run speed, only a walk while exhausted
+    pub fn run_speed(&self) -> f32 {
+        if self.exhausted {
+            self.max_speed * Player::EXHAUSTED_SPEED
+        } else {
+            self.max_speed
+        }
+    }
+}
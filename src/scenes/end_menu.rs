@@ -0,0 +1,125 @@
+use crate::{
+    scenes::{GlobalEnvironment, SceneID},
+    versus::{self, MatchMode, MatchOutcome},
+};
+use rayexlib::prelude::Scene;
+use raylib::prelude::{
+    measure_text, Color, KeyboardKey, RaylibDraw, RaylibDrawHandle, RaylibHandle, RaylibThread,
+};
+
+/// End menu constant values
+const BACKGROUND_TOP_COLOR: Color = Color::new(40, 20, 60, 255);
+const BACKGROUND_BOTTOM_COLOR: Color = Color::new(10, 10, 10, 255);
+const TITLE_SIZE: i32 = 80;
+const TEXT_SIZE: i32 = 30;
+const ROW_HEIGHT: i32 = 50;
+/// Stat columns & their share of the screen width from the left
+const COLUMNS: [(&str, f32); 6] = [
+    ("Player", 0.1),
+    ("Damage dealt", 0.22),
+    ("KOs", 0.42),
+    ("Falls", 0.52),
+    ("SDs", 0.62),
+    ("Time alive", 0.74),
+];
+
+/// Results of the last match & rules of the next one
+pub struct Environment {}
+
+impl Environment {
+    pub fn init(raylib: &mut RaylibHandle, thread: &RaylibThread) -> Self {
+        Self {}
+    }
+}
+
+impl Scene<GlobalEnvironment> for Environment {
+    fn update(&mut self, global: &mut GlobalEnvironment, raylib: &mut RaylibHandle) {
+        // pick the next match's rules
+        if raylib.is_key_pressed(KeyboardKey::KEY_TAB) {
+            global.rules.mode = global.rules.mode.switched();
+        }
+        if raylib.is_key_pressed(KeyboardKey::KEY_RIGHT) {
+            global.rules.mode = global.rules.mode.adjusted(1);
+        }
+        if raylib.is_key_pressed(KeyboardKey::KEY_LEFT) {
+            global.rules.mode = global.rules.mode.adjusted(-1);
+        }
+
+        // rematch or leave
+        if raylib.is_key_pressed(KeyboardKey::KEY_ENTER) {
+            global.next_scene = Some(SceneID::World);
+        }
+        if raylib.is_key_pressed(KeyboardKey::KEY_BACKSPACE) {
+            global.next_scene = Some(SceneID::MainMenu);
+        }
+    }
+
+    fn draw(&self, global: &GlobalEnvironment, raylib: &mut RaylibDrawHandle, alpha: f32) {
+        let win_width = raylib.get_screen_width();
+        let win_height = raylib.get_screen_height();
+        let versus = &global.versus;
+
+        // background gradient
+        raylib.draw_rectangle_gradient_v(
+            0,
+            0,
+            win_width,
+            win_height,
+            BACKGROUND_TOP_COLOR,
+            BACKGROUND_BOTTOM_COLOR,
+        );
+
+        // winner
+        let (title, title_color) = match &versus.outcome {
+            Some(MatchOutcome::Winner(id)) => (
+                format!("P{} wins!", id + 1),
+                global.players.get(*id).map_or(Color::WHITE, |p| p.color),
+            ),
+            Some(MatchOutcome::Draw(_)) => ("Draw!".to_string(), Color::WHITE),
+            None => ("No contest".to_string(), Color::GRAY),
+        };
+        let title_x = (win_width - measure_text(&title, TITLE_SIZE)) / 2;
+        raylib.draw_text(&title, title_x, win_height / 10, TITLE_SIZE, title_color);
+
+        // stats table, best player first
+        let column_x = |share: f32| (win_width as f32 * share) as i32;
+        let table_y = win_height / 10 + TITLE_SIZE + ROW_HEIGHT;
+        for (name, share) in COLUMNS {
+            raylib.draw_text(name, column_x(share), table_y, TEXT_SIZE, Color::GRAY);
+        }
+
+        for (row, id) in versus.ranking().into_iter().enumerate() {
+            let record = &versus.records[id];
+            let color = global.players.get(id).map_or(Color::WHITE, |p| p.color);
+            let y = table_y + (row as i32 + 1) * ROW_HEIGHT;
+            let values = [
+                format!("P{}", id + 1),
+                format!("{:.0}", record.damage_dealt),
+                record.kos.to_string(),
+                record.falls.to_string(),
+                record.self_destructs.to_string(),
+                versus::clock(record.time_alive),
+            ];
+
+            for ((_, share), value) in COLUMNS.iter().zip(&values) {
+                raylib.draw_text(value, column_x(*share), y, TEXT_SIZE, color);
+            }
+        }
+
+        // next match
+        let mode = match global.rules.mode {
+            MatchMode::Stock(stocks) => format!("Next: {} stock", stocks),
+            MatchMode::Timed(time) => format!("Next: {} timed", versus::clock(time)),
+        };
+        let hints = "Enter: rematch   Tab: mode   Left/Right: length   Backspace: menu";
+        let y = win_height - win_height / 6;
+        raylib.draw_text(&mode, column_x(COLUMNS[0].1), y, TEXT_SIZE, Color::GOLD);
+        raylib.draw_text(
+            hints,
+            column_x(COLUMNS[0].1),
+            y + ROW_HEIGHT,
+            TEXT_SIZE / 3 * 2,
+            Color::LIGHTGRAY,
+        );
+    }
+}
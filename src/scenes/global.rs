@@ -1,17 +1,50 @@
-use crate::player::Player;
+use super::{world, SceneID};
+use crate::{
+    player::{Controls, Player},
+    versus::{Match, MatchRules},
+};
 use rayexlib::prelude::{CameraEx2D, Init};
-use raylib::{RaylibHandle, RaylibThread};
+use raylib::{prelude::Color, RaylibHandle, RaylibThread};
+
+/// Local players in a match
+const PLAYERS: usize = 2;
+/// Sprite tint of the second player
+const PLAYER_TWO_COLOR: Color = Color::new(140, 180, 255, 255);
 
 pub struct GlobalEnvironment {
     pub camera: CameraEx2D,
-    pub player: Player,
+    /// Local players, indexed by their id
+    pub players: Vec<Player>,
+    /// Rules of the next match
+    pub rules: MatchRules,
+    /// Match being fought, or the last one
+    pub versus: Match,
+    /// Scene to switch to after this frame's update
+    pub next_scene: Option<SceneID>,
 }
 
 impl GlobalEnvironment {
     pub fn init(raylib: &mut RaylibHandle, thread: &RaylibThread) -> Self {
+        let rules = MatchRules::default();
+
         Self {
             camera: CameraEx2D::default().with_move_speed(7.0),
-            player: Player::init(raylib, thread),
+            players: vec![
+                Player::init(raylib, thread).with_controls(Controls::wasd().with_gamepad(0)),
+                Player::init(raylib, thread)
+                    .with_controls(Controls::arrows().with_gamepad(1))
+                    .with_color(PLAYER_TWO_COLOR),
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(id, player)| {
+                let start = world::start_point(id);
+                player.with_position(start.x, start.y)
+            })
+            .collect(),
+            rules,
+            versus: Match::new(rules, PLAYERS),
+            next_scene: None,
         }
     }
 }
@@ -47,7 +47,7 @@ impl Scene<GlobalEnvironment> for Environment {
             .set_offset_xy(self.icon.half_width(), self.icon.half_width());
     }
 
-    fn draw(&self, global: &GlobalEnvironment, raylib: &mut RaylibDrawHandle) {
+    fn draw(&self, global: &GlobalEnvironment, raylib: &mut RaylibDrawHandle, alpha: f32) {
         let win_width = raylib.get_screen_width() as f32;
         let win_height = raylib.get_screen_height() as f32;
 
@@ -90,7 +90,7 @@ impl Scene<GlobalEnvironment> for Environment {
         self.parallax_close.update(raylib);
     }
 
-    fn draw(&self, global: &GlobalEnvironment, raylib: &mut RaylibDrawHandle) {
+    fn draw(&self, global: &GlobalEnvironment, raylib: &mut RaylibDrawHandle, alpha: f32) {
         // draw background color
         raylib.clear_background(BACKGROUND);
 
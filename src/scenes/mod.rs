@@ -2,6 +2,7 @@ use rayexlib::prelude::SceneManager;
 use raylib::prelude::{RaylibHandle, RaylibThread};
 use std::{fmt::Debug, hash::Hash};
 
+mod end_menu;
 mod global;
 mod loading;
 mod main_menu;
@@ -10,7 +11,7 @@ pub mod world;
 
 pub use global::GlobalEnvironment;
 
-#[derive(Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SceneID {
     Loading,
     MainMenu,
@@ -33,6 +34,7 @@ impl SceneInitializer for SceneManager<SceneID, GlobalEnvironment> {
         let world = world::Environment::init(raylib, thread);
         let loading = loading::Environment::init(raylib, thread);
         let pause_menu = pause_menu::Environment::init(raylib, thread);
+        let end_menu = end_menu::Environment::init(raylib, thread);
 
         // add scenes to scene machine
         let mut machine = SceneManager::default();
@@ -40,6 +42,7 @@ impl SceneInitializer for SceneManager<SceneID, GlobalEnvironment> {
         machine.add_scene(SceneID::World, world);
         machine.add_scene(SceneID::Loading, loading);
         machine.add_scene(SceneID::PauseMenu, pause_menu);
+        machine.add_scene(SceneID::EndMenu, end_menu);
         machine
     }
 }
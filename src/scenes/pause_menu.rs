@@ -15,7 +15,7 @@ impl Environment {
 impl Scene<GlobalEnvironment> for Environment {
     fn update(&mut self, global: &mut GlobalEnvironment, raylib: &mut RaylibHandle) {}
 
-    fn draw(&self, global: &GlobalEnvironment, raylib: &mut RaylibDrawHandle) {
+    fn draw(&self, global: &GlobalEnvironment, raylib: &mut RaylibDrawHandle, alpha: f32) {
         // draw background color
         raylib.clear_background(BACKGROUND);
     }
@@ -1,19 +1,271 @@
-use crate::scenes::GlobalEnvironment;
-use rayexlib::prelude::{ColliderInfo, Rect2D, Renderable, Scene};
+use crate::{
+    combat::{detect_hits, ActiveHitbox, AttackData, AttackResults, Attacker, Hurtbox},
+    layers, paths,
+    player::{Player, PlayerState},
+    scenes::{GlobalEnvironment, SceneID},
+    versus::{self, Match, MatchMode, MatchPhase},
+};
+use rayexlib::prelude::{
+    Area2D, AreaEvent, BodyShape2D, CameraEx2D, Circle2D, ColliderInfo, CollisionLayer,
+    CollisionObject, ImagePlugin, PathMode, Platform2D, Polygon2D, Ray2D, Rect2D, Renderable,
+    RigidBody2D, Scene, Segment2D, SpatialHash, Texture2DPlugin,
+};
 use raylib::prelude::{
-    Color, RaylibDraw, RaylibDrawHandle, RaylibHandle, RaylibMode2DExt, RaylibThread,
+    measure_text, Color, Image, RaylibDraw, RaylibDrawHandle, RaylibHandle, RaylibMode2DExt,
+    RaylibThread, Rectangle, Texture2D, Vector2,
 };
 
 const BACKGROUND_COLOR: Color = Color::new(25, 25, 25, 255);
+const BROAD_PHASE_CELL_SIZE: f32 = 256.0;
+const START_POINT: Vector2 = Vector2::new(100.0, -100.0);
+/// Distance between players spawning side by side
+const SPAWN_SPACING: f32 = 200.0;
+/// Part of the overlap between two players pushed apart each step
+const PLAYER_PUSH: f32 = 0.25;
+/// Screen space kept around the players by the camera
+const CAMERA_PADDING: f32 = 200.0;
+/// Attacker id shared by all level hazards
+const HAZARD_ID: usize = usize::MAX;
+const GRAVITY: Vector2 = Vector2::new(0.0, 1500.0);
+/// Alpha above which prop pixels are solid
+const PROP_ALPHA_THRESHOLD: u8 = 127;
+/// Pixels a traced prop outline may stray from the sprite
+const PROP_OUTLINE_TOLERANCE: f32 = 2.0;
+/// Simulation steps frozen when a hit lands
+const HIT_STOP_STEPS: u32 = 5;
+
+/// Index into one of the terrain lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum TerrainId {
+    Floor(usize),
+    Slope(usize),
+    Ledge(usize),
+    Platform(usize),
+    Body(usize),
+    Prop(usize),
+}
+
+/// Tileset sprite with a collider traced from its alpha
+struct Prop {
+    source: Rectangle,
+    collider: Polygon2D,
+}
+
+/// First spawn point of player `id` (players are ids into `GlobalEnvironment::players`)
+pub fn start_point(id: usize) -> Vector2 {
+    START_POINT + Vector2::new(id as f32 * SPAWN_SPACING, 0.0)
+}
+
+/// What happens when a player touches a trigger area
+enum Trigger {
+    /// Knock out, respawning at the last checkpoint
+    KillZone,
+    /// Move the respawn point
+    Checkpoint(Vector2),
+    /// Limit camera zoom while any player is inside
+    CameraZone(f32),
+    /// Leave the level, only before the match starts
+    LevelExit(SceneID),
+}
+
+impl Trigger {
+    fn color(&self) -> Color {
+        match self {
+            Trigger::KillZone => Color::RED,
+            Trigger::Checkpoint(_) => Color::GREEN,
+            Trigger::CameraZone(_) => Color::SKYBLUE,
+            Trigger::LevelExit(_) => Color::GOLD,
+        }
+    }
+}
 
 pub struct Environment {
     // camera: CameraEx2D,
     // player: Player,
     floors: Vec<Rect2D>,
+    slopes: Vec<Polygon2D>,
+    ledges: Vec<Segment2D>,
+    platforms: Vec<Platform2D>,
+    bodies: Vec<RigidBody2D>,
+    props_texture: Texture2D,
+    props: Vec<Prop>,
+    terrain_grid: SpatialHash<TerrainId>,
+    triggers: Vec<(Trigger, Area2D<Rect2D, usize>)>,
+    hazards: Vec<ActiveHitbox>,
+    /// Steps left frozen on impact
+    hit_stop: u32,
+    /// Leaving it knocks the player out
+    blast_zone: Rectangle,
+    spawn_point: Vector2,
 }
 
 impl Environment {
     pub fn init(raylib: &mut RaylibHandle, thread: &RaylibThread) -> Self {
+        let floors = vec![
+            Rect2D::new(2000.0, 100.0).with_position_center(0.0, 200.0),
+            Rect2D::new(100.0, 100.0).with_position_center(0.0, 100.0),
+            Rect2D::new(100.0, 500.0).with_position_center(500.0, 100.0),
+            Rect2D::new(300.0, 20.0)
+                .with_position_center(-400.0, 0.0)
+                .with_one_way(),
+            // low ceiling, only passable crouched
+            Rect2D::new(160.0, 40.0).with_position_center(340.0, 40.0),
+        ];
+
+        let slopes = vec![
+            // ramps up & down the box
+            Polygon2D::right_triangle(Vector2::new(200.0, 100.0), false)
+                .with_position(-150.0, 100.0),
+            Polygon2D::right_triangle(Vector2::new(200.0, 100.0), true).with_position(150.0, 100.0),
+            // hill
+            Polygon2D::from(vec![
+                Vector2::new(-175.0, 40.0),
+                Vector2::new(-60.0, -40.0),
+                Vector2::new(60.0, -40.0),
+                Vector2::new(175.0, 40.0),
+            ])
+            .with_position(-800.0, 110.0),
+            // concave dip, split into convex parts
+            Polygon2D::from(vec![
+                Vector2::new(-100.0, -30.0),
+                Vector2::new(-70.0, -30.0),
+                Vector2::new(-50.0, 10.0),
+                Vector2::new(50.0, 10.0),
+                Vector2::new(70.0, -30.0),
+                Vector2::new(100.0, -30.0),
+                Vector2::new(100.0, 30.0),
+                Vector2::new(-100.0, 30.0),
+            ])
+            .with_position(-400.0, 120.0),
+        ];
+
+        let ledges = vec![
+            // thin ledge past the elevator, jump up through it
+            Segment2D::new(Vector2::new(760.0, -280.0), Vector2::new(950.0, -280.0)).with_one_way(),
+        ];
+
+        let platforms = vec![
+            // ferry over the one way floor
+            Platform2D::new(
+                Rect2D::new(150.0, 20.0),
+                vec![Vector2::new(-750.0, -150.0), Vector2::new(-350.0, -150.0)],
+                150.0,
+            )
+            .with_mode(PathMode::PingPong)
+            .with_wait_time(0.5),
+            // elevator next to the wall
+            Platform2D::new(
+                Rect2D::new(150.0, 20.0),
+                vec![Vector2::new(600.0, 130.0), Vector2::new(600.0, -250.0)],
+                100.0,
+            )
+            .with_mode(PathMode::PingPong)
+            .with_wait_time(1.0),
+        ];
+
+        let bodies = vec![
+            // crates on the box
+            RigidBody2D::new(
+                BodyShape2D::Rect(Rect2D::new(50.0, 50.0).with_position(-40.0, -50.0)),
+                2.0,
+            )
+            .with_friction(0.6),
+            RigidBody2D::new(
+                BodyShape2D::Rect(Rect2D::new(50.0, 50.0).with_position(-20.0, -150.0)),
+                2.0,
+            )
+            .with_friction(0.6),
+            // ball bouncing down the hill
+            RigidBody2D::new(
+                BodyShape2D::Circle(Circle2D::new(25.0).with_position(-800.0, -200.0)),
+                1.0,
+            )
+            .with_restitution(0.7)
+            .with_damping(0.1, 0.5),
+            // tumbling rock
+            RigidBody2D::new(
+                BodyShape2D::Polygon(Polygon2D::new(60.0, 6).with_position(300.0, -200.0)),
+                3.0,
+            )
+            .with_velocity(Vector2::new(-100.0, 0.0)),
+        ];
+
+        // props, colliders traced from the sprite outlines
+        let props_image = Image::from_path(paths::world::PROPS);
+        let props_texture = Texture2D::from_path(raylib, thread, paths::world::PROPS);
+        let props: Vec<Prop> = [
+            // rock by the wall
+            (
+                Rectangle::new(176.0, 96.0, 160.0, 64.0),
+                Vector2::new(700.0, 118.0),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(source, position)| {
+            let collider = props_image
+                .alpha_polygon(source, PROP_ALPHA_THRESHOLD, PROP_OUTLINE_TOLERANCE)?
+                .with_position(position.x, position.y);
+            Some(Prop { source, collider })
+        })
+        .collect();
+
+        // terrain broad phase
+        let mut terrain_grid = SpatialHash::new(BROAD_PHASE_CELL_SIZE);
+        for (i, floor) in floors.iter().enumerate() {
+            terrain_grid.insert(TerrainId::Floor(i), floor);
+        }
+        for (i, slope) in slopes.iter().enumerate() {
+            terrain_grid.insert(TerrainId::Slope(i), slope);
+        }
+        for (i, ledge) in ledges.iter().enumerate() {
+            terrain_grid.insert(TerrainId::Ledge(i), ledge);
+        }
+        for (i, platform) in platforms.iter().enumerate() {
+            terrain_grid.insert(TerrainId::Platform(i), platform);
+        }
+        for (i, body) in bodies.iter().enumerate() {
+            terrain_grid.insert(TerrainId::Body(i), body);
+        }
+        for (i, prop) in props.iter().enumerate() {
+            terrain_grid.insert(TerrainId::Prop(i), &prop.collider);
+        }
+
+        let trigger_area = |width, height, x, y| {
+            Area2D::new(
+                Rect2D::new(width, height)
+                    .with_position_center(x, y)
+                    .with_layer(layers::TRIGGER)
+                    .with_mask(layers::TRIGGER_MASK),
+            )
+        };
+
+        let triggers = vec![
+            (Trigger::KillZone, trigger_area(10000.0, 200.0, 0.0, 1500.0)),
+            (
+                Trigger::Checkpoint(Vector2::new(-840.0, -150.0)),
+                trigger_area(60.0, 200.0, -800.0, -30.0),
+            ),
+            (
+                Trigger::CameraZone(0.6),
+                trigger_area(600.0, 400.0, 0.0, -50.0),
+            ),
+            (
+                Trigger::LevelExit(SceneID::MainMenu),
+                trigger_area(100.0, 300.0, 900.0, 0.0),
+            ),
+        ];
+
+        let hazards = vec![
+            // spikes between the hill & the dip, launch straight up
+            ActiveHitbox {
+                rect: Rect2D::new(80.0, 16.0)
+                    .with_position_center(-560.0, 142.0)
+                    .with_layer(layers::HITBOX)
+                    .with_mask(layers::HITBOX_MASK),
+                data: AttackData::new(10.0, 700.0, 90.0, 0.4),
+            },
+        ];
+
         Self {
             // camera: CameraEx2D::default()
             //     .with_offset(
@@ -22,52 +274,409 @@ impl Environment {
             //     )
             //     .with_move_speed(7.0),
             // player: Player::init(raylib, thread),
-            floors: vec![
-                Rect2D::new(2000.0, 100.0).with_position_center(0.0, 200.0),
-                Rect2D::new(100.0, 100.0).with_position_center(0.0, 100.0),
-                Rect2D::new(100.0, 500.0).with_position_center(500.0, 100.0),
-            ],
+            floors,
+            slopes,
+            ledges,
+            platforms,
+            bodies,
+            props_texture,
+            props,
+            terrain_grid,
+            triggers,
+            hazards,
+            hit_stop: 0,
+            blast_zone: Rectangle::new(-2500.0, -2000.0, 5000.0, 3200.0),
+            spawn_point: START_POINT,
+        }
+    }
+
+    fn terrain(&self, id: TerrainId) -> &dyn CollisionObject {
+        match id {
+            TerrainId::Floor(i) => &self.floors[i],
+            TerrainId::Slope(i) => &self.slopes[i],
+            TerrainId::Ledge(i) => &self.ledges[i],
+            TerrainId::Platform(i) => &self.platforms[i],
+            TerrainId::Body(i) => &self.bodies[i],
+            TerrainId::Prop(i) => &self.props[i].collider,
+        }
+    }
+
+    /// Step rigid bodies against the terrain around them
+    fn simulate_bodies(&mut self, frame_time: f32) {
+        // area covered by every body & its motion this frame
+        let Some(area) = self
+            .bodies
+            .iter()
+            .map(|body| {
+                let bounds = body.bounds();
+                let motion = body.velocity * frame_time;
+                (
+                    Vector2::new(bounds.x + motion.x.min(0.0), bounds.y + motion.y.min(0.0)),
+                    Vector2::new(
+                        bounds.x + bounds.width + motion.x.max(0.0),
+                        bounds.y + bounds.height + motion.y.max(0.0),
+                    ),
+                )
+            })
+            .reduce(|(min_a, max_a), (min_b, max_b)| {
+                (
+                    Vector2::new(min_a.x.min(min_b.x), min_a.y.min(min_b.y)),
+                    Vector2::new(max_a.x.max(max_b.x), max_a.y.max(max_b.y)),
+                )
+            })
+            .map(|(min, max)| Rectangle::new(min.x, min.y, max.x - min.x, max.y - min.y))
+        else {
+            return;
+        };
+
+        // bodies collide with each other inside simulate
+        let terrain: Vec<&dyn CollisionObject> = self
+            .terrain_grid
+            .query_rect(area, CollisionLayer::ALL)
+            .into_iter()
+            .filter(|id| !matches!(id, TerrainId::Body(_)))
+            .map(|id| match id {
+                TerrainId::Floor(i) => &self.floors[i] as &dyn CollisionObject,
+                TerrainId::Slope(i) => &self.slopes[i],
+                TerrainId::Ledge(i) => &self.ledges[i],
+                TerrainId::Platform(i) => &self.platforms[i],
+                TerrainId::Prop(i) => &self.props[i].collider,
+                TerrainId::Body(_) => unreachable!(),
+            })
+            .collect();
+
+        RigidBody2D::simulate(&mut self.bodies, &terrain, GRAVITY, frame_time);
+
+        for (i, body) in self.bodies.iter().enumerate() {
+            self.terrain_grid.move_shape(TerrainId::Body(i), body);
+        }
+    }
+
+    /// Spawn point of player `id`, next to the others
+    fn spawn_point(&self, id: usize) -> Vector2 {
+        self.spawn_point + Vector2::new(id as f32 * SPAWN_SPACING, 0.0)
+    }
+
+    /// Hits landed this step by players & hazards, stunned players are safe from hazards
+    fn detect_hits(&self, players: &[Player]) -> AttackResults<usize> {
+        let stunned: Vec<usize> = (0..players.len())
+            .filter(|&id| players[id].hitstun > 0.0)
+            .collect();
+        let mut attackers: Vec<Attacker<usize>> = players
+            .iter()
+            .enumerate()
+            .map(|(id, player)| Attacker {
+                id,
+                facing: player.facing,
+                hitboxes: &player.hitboxes,
+                already_hit: &player.hit_targets,
+                clashes: true,
+            })
+            .collect();
+        attackers.push(Attacker {
+            id: HAZARD_ID,
+            facing: 1.0,
+            hitboxes: &self.hazards,
+            already_hit: &stunned,
+            clashes: false,
+        });
+
+        let rects: Vec<Rect2D> = players.iter().map(Player::hurtbox).collect();
+        let hurtboxes: Vec<Hurtbox<usize>> = players
+            .iter()
+            .zip(&rects)
+            .enumerate()
+            .filter(|(_, (player, _))| !player.is_invulnerable())
+            .map(|(id, (player, rect))| Hurtbox {
+                id,
+                rect,
+                pixels: player.sprite_mask(),
+            })
+            .collect();
+
+        detect_hits(&attackers, &hurtboxes)
+    }
+
+    /// New match with the current rules, players back at the level start
+    fn start_match(&mut self, global: &mut GlobalEnvironment, raylib: &mut RaylibHandle) {
+        self.spawn_point = START_POINT;
+        global.versus = Match::new(global.rules, global.players.len());
+        for (id, player) in global.players.iter_mut().enumerate() {
+            player.reset(start_point(id), raylib);
+        }
+    }
+
+    /// Freeze the simulation for a moment on impact
+    fn start_hit_stop(&mut self, global: &mut GlobalEnvironment) {
+        self.hit_stop = HIT_STOP_STEPS;
+
+        // hold sprites still, animations only advance with simulation steps
+        for player in &mut global.players {
+            player.body.reset_interpolation();
+        }
+        for platform in &mut self.platforms {
+            platform.reset_interpolation();
+        }
+    }
+
+    fn on_trigger(
+        &mut self,
+        index: usize,
+        event: AreaEvent<usize>,
+        global: &mut GlobalEnvironment,
+        raylib: &mut RaylibHandle,
+    ) {
+        match (&self.triggers[index].0, event) {
+            (Trigger::KillZone, AreaEvent::Enter(id)) => {
+                global.players[id].knock_out(raylib);
+            }
+            (Trigger::Checkpoint(point), AreaEvent::Enter(_)) => {
+                self.spawn_point = *point;
+            }
+            (Trigger::LevelExit(scene), AreaEvent::Enter(_))
+                if matches!(global.versus.phase, MatchPhase::Countdown(_)) =>
+            {
+                // only before the fight, a started match plays out to the results
+                let scene = *scene;
+                self.start_match(global, raylib);
+                global.next_scene = Some(scene);
+            }
+            _ => {}
         }
     }
 }
 
 impl Scene<GlobalEnvironment> for Environment {
-    fn update(&mut self, global: &mut GlobalEnvironment, raylib: &mut RaylibHandle) {
-        let player = &mut global.player;
+    fn on_enter(&mut self, global: &mut GlobalEnvironment, raylib: &mut RaylibHandle) {
+        // rematch, with the rules picked on the results screen
+        if global.versus.is_over() {
+            self.start_match(global, raylib);
+        }
+    }
 
-        player.update(raylib);
+    fn fixed_update(
+        &mut self,
+        global: &mut GlobalEnvironment,
+        raylib: &mut RaylibHandle,
+        time_step: f32,
+    ) {
+        // hit-stop
+        if self.hit_stop > 0 {
+            self.hit_stop -= 1;
+            return;
+        }
+
+        // no control before the countdown ends or after the match
+        for player in &mut global.players {
+            player.frame_time = time_step;
+            player.locked = !global.versus.is_fighting();
+            player.update(raylib);
+        }
+
+        // move platforms before the players ride them
+        for (i, platform) in self.platforms.iter_mut().enumerate() {
+            platform.update(time_step);
+            self.terrain_grid
+                .move_shape(TerrainId::Platform(i), platform);
+        }
+        self.simulate_bodies(time_step);
 
-        global.camera.follow_bound(player.velocity(), 100.0, 7.0);
-        global.camera.follow(player.center(), raylib);
+        // players pushed apart first, terrain resolution keeps them out of walls
+        push_apart(&mut global.players);
 
         // collision
-        {
-            player.reset_colliding();
-            player.collide_rects(raylib, &mut self.floors);
+        for player in &mut global.players {
+            let terrain: Vec<&dyn CollisionObject> = self
+                .terrain_grid
+                .query_rect(player.motion_bounds(), player.body.mask())
+                .into_iter()
+                .map(|id| self.terrain(id))
+                .collect();
+            player.collide_terrain(raylib, &terrain);
+
+            // squeezed by a platform
+            if player.body.is_crushed() {
+                player.knock_out(raylib);
+            }
+        }
+
+        // trigger areas
+        let bodies: Vec<(usize, &dyn CollisionObject)> = global
+            .players
+            .iter()
+            .enumerate()
+            .map(|(id, player)| (id, &player.body as &dyn CollisionObject))
+            .collect();
+        let events: Vec<(usize, AreaEvent<usize>)> = self
+            .triggers
+            .iter_mut()
+            .enumerate()
+            .flat_map(|(i, (_, area))| {
+                area.update(bodies.iter().copied())
+                    .into_iter()
+                    .map(move |event| (i, event))
+            })
+            .collect();
+
+        for (i, event) in events {
+            self.on_trigger(i, event, global, raylib);
+        }
+
+        // closest zoom limit of the camera zones any player is in
+        global.camera.zoom_maximum = self
+            .triggers
+            .iter()
+            .filter_map(|(trigger, area)| match trigger {
+                Trigger::CameraZone(zoom) if !area.overlapping().is_empty() => Some(*zoom),
+                _ => None,
+            })
+            .reduce(f32::min)
+            .unwrap_or(CameraEx2D::default().zoom_maximum);
+
+        // attacks & hazards
+        let AttackResults { hits, cancelled } = self.detect_hits(&global.players);
+        for id in cancelled {
+            if let Some(player) = global.players.get_mut(id) {
+                player.cancel_attack();
+            }
+        }
+        for hit in &hits {
+            if let Some(attacker) = global.players.get_mut(hit.attacker) {
+                attacker.hit_targets.push(hit.target);
+            }
+            global.players[hit.target].take_hit(hit);
+            global
+                .versus
+                .record_hit(hit.attacker, hit.target, hit.data.damage);
+        }
+
+        // knocked out by damage or leaving the stage
+        for player in &mut global.players {
+            if player.is_knocked_out(&self.blast_zone) {
+                player.knock_out(raylib);
+            }
+        }
+        global.versus.update(&global.players, time_step);
+
+        // back at the spawn point after a while, unless out of stocks
+        for id in 0..global.players.len() {
+            let spawn_point = self.spawn_point(id);
+            let player = &mut global.players[id];
+            if player.can_respawn() && !global.versus.is_eliminated(id) {
+                player.respawn(spawn_point, raylib);
+            }
+        }
+
+        if global.versus.is_over() {
+            global.next_scene = Some(SceneID::EndMenu);
+        }
+
+        if !hits.is_empty() {
+            self.start_hit_stop(global);
         }
     }
 
-    fn draw(&self, global: &GlobalEnvironment, raylib: &mut RaylibDrawHandle) {
-        let player = &global.player;
+    fn update(&mut self, global: &mut GlobalEnvironment, raylib: &mut RaylibHandle) {
+        // keep every player still in the fight in view
+        let standing: Vec<&Player> = global
+            .players
+            .iter()
+            .filter(|player| player.state != PlayerState::Dead)
+            .collect();
+        let followed = if standing.is_empty() {
+            global.players.iter().collect()
+        } else {
+            standing
+        };
+
+        let Some(first) = followed.first() else {
+            return;
+        };
+        let (mut min, mut max) = (first.center(), first.center());
+        let mut fastest = Vector2::zero();
+        for player in &followed {
+            let center = player.center();
+            min = Vector2::new(min.x.min(center.x), min.y.min(center.y));
+            max = Vector2::new(max.x.max(center.x), max.y.max(center.y));
+            if player.velocity().length() > fastest.length() {
+                fastest = player.velocity();
+            }
+        }
+
+        let area = Rectangle::new(min.x, min.y, max.x - min.x, max.y - min.y);
+        global.camera.follow_bound(fastest, 100.0, 7.0);
+        global.camera.fit(area, CAMERA_PADDING, raylib);
+        global.camera.follow((min + max) / 2.0, raylib);
+    }
 
+    fn draw(&self, global: &GlobalEnvironment, raylib: &mut RaylibDrawHandle, alpha: f32) {
         raylib.clear_background(BACKGROUND_COLOR);
 
         // enter camera
         let mut rl = raylib.begin_mode2D(&global.camera);
         {
-            player.draw(&mut rl);
+            // eliminated players leave the stage
+            for (id, player) in global.players.iter().enumerate() {
+                if !global.versus.is_eliminated(id) {
+                    player.draw_interpolated(alpha, &mut rl);
+                }
+            }
 
             self.floors.iter().for_each(|floor| {
-                floor.draw(Color::BEIGE, &mut rl);
+                // one way floors are lighter
+                let color = if floor.one_way {
+                    Color::LIGHTGRAY
+                } else {
+                    Color::BEIGE
+                };
+                floor.draw(color, &mut rl);
+            });
+
+            self.slopes.iter().for_each(|slope| {
+                slope.draw(Color::BEIGE, &mut rl);
+            });
+
+            self.ledges.iter().for_each(|ledge| {
+                ledge.draw(Color::LIGHTGRAY, &mut rl);
+            });
+
+            self.platforms.iter().for_each(|platform| {
+                platform.draw_interpolated(alpha, Color::BROWN, &mut rl);
+            });
+
+            self.bodies.iter().for_each(|body| {
+                body.draw(Color::ORANGE, &mut rl);
+            });
+
+            self.hazards.iter().for_each(|hazard| {
+                hazard.rect.draw(Color::MAROON, &mut rl);
+            });
+
+            self.props.iter().for_each(|prop| {
+                let size = Vector2::new(prop.source.width, prop.source.height);
+                rl.draw_texture_rec(
+                    &self.props_texture,
+                    prop.source,
+                    prop.collider.position - size / 2.0,
+                    Color::WHITE,
+                );
             });
 
             // exit camera
             drop(rl);
         }
+
+        draw_hud(&global.players, &global.versus, raylib);
+        draw_match_banner(&global.versus, raylib);
     }
 
     fn debug(&self, global: &GlobalEnvironment, raylib: &mut RaylibDrawHandle) {
-        let player = &global.player;
+        let player = &global.players[0];
+
+        // line of sight from the player to the mouse
+        let mouse = raylib.get_screen_to_world2D(raylib.get_mouse_position(), &global.camera);
+        let sight = Ray2D::between(player.center(), mouse).with_mask(layers::TERRAIN);
+        let sight_hit = self.terrain_grid.raycast(&sight, |id| self.terrain(id));
 
         // start drawing within camera
         let mut rl = raylib.begin_mode2D(&global.camera);
@@ -75,8 +684,57 @@ impl Scene<GlobalEnvironment> for Environment {
             // world origin reference point
             rl.draw_circle(0, 0, 10.0, Color::PINK);
 
-            player.collider.draw(Color::WHITE, &mut rl);
-            player.ground_ray.draw(Color::RED, &mut rl);
+            // broad phase cells
+            self.terrain_grid.draw(Color::DARKGRAY, &mut rl);
+
+            // trigger areas, filled while a player is inside
+            for (trigger, area) in &self.triggers {
+                area.shape.draw(trigger.color(), &mut rl);
+                if !area.overlapping().is_empty() {
+                    rl.draw_rectangle_rec(area.bounds(), trigger.color().fade(0.25));
+                }
+            }
+
+            // platform paths
+            for platform in &self.platforms {
+                platform.draw_path(Color::BROWN, &mut rl);
+            }
+
+            // traced prop colliders
+            for prop in &self.props {
+                prop.collider.draw(Color::LIME, &mut rl);
+            }
+
+            // respawn points & blast zone
+            for id in 0..global.players.len() {
+                rl.draw_circle_v(self.spawn_point(id), 10.0, Color::GREEN);
+            }
+            rl.draw_rectangle_lines_ex(self.blast_zone, 2, Color::RED.fade(0.5));
+
+            for player in &global.players {
+                player.body.draw(Color::WHITE, &mut rl);
+                player.ground_ray.draw(Color::RED, &mut rl);
+
+                // attack boxes
+                for hitbox in &player.hitboxes {
+                    hitbox.rect.draw(Color::RED, &mut rl);
+                }
+
+                // sprite pixels, brighter under the mouse
+                if let Some(mask) = player.sprite_mask() {
+                    let alpha = if mask.contains_point(mouse) { 0.6 } else { 0.2 };
+                    mask.draw(Color::SKYBLUE.fade(alpha), &mut rl);
+                }
+            }
+
+            // line of sight, cut short by the first terrain hit
+            match &sight_hit {
+                Some(hit) => {
+                    rl.draw_line_v(sight.position, hit.point, Color::YELLOW);
+                    hit.draw(Color::YELLOW, &mut rl);
+                }
+                None => rl.draw_line_v(sight.position, sight.end(), Color::YELLOW.fade(0.5)),
+            }
 
             let (x, y) = (player.center().x as i32, player.center().y as i32);
             rl.draw_circle_lines(x, y, 100.0, Color::GOLD);
@@ -86,8 +744,8 @@ impl Scene<GlobalEnvironment> for Environment {
         }
 
         // information
-        let pos = player.collider.position;
-        let vel = player.collider.velocity;
+        let pos = player.body.collider.position;
+        let vel = player.body.collider.velocity;
 
         // debug info / text color
         let debug_info = [
@@ -106,13 +764,23 @@ impl Scene<GlobalEnvironment> for Environment {
                 ),
             ),
             (Color::PINK, format!("player state: {:#?}", player.state)),
+            (
+                Color::RED,
+                format!(
+                    "player health: {} : {} (stun {:.2}s)",
+                    player.max_health.round(),
+                    player.health.round(),
+                    player.hitstun
+                ),
+            ),
             (
                 Color::SKYBLUE,
                 format!(
-                    "player energy: {} : {} ({}%)",
+                    "player energy: {} : {} ({}%){}",
                     player.max_stamina.round(),
                     player.stamina.round(),
-                    ((player.stamina / player.max_stamina) * 100.0).round()
+                    ((player.stamina / player.max_stamina) * 100.0).round(),
+                    if player.exhausted { " exhausted" } else { "" }
                 ),
             ),
             (
@@ -128,3 +796,100 @@ impl Scene<GlobalEnvironment> for Environment {
         });
     }
 }
+
+/// Players overlapping each other are pushed apart sideways, a bit every step,
+/// before terrain is resolved (knocked out & respawning players pass through)
+fn push_apart(players: &mut [Player]) {
+    let solid =
+        |player: &Player| !matches!(player.state, PlayerState::Dead | PlayerState::Respawning);
+
+    for i in 0..players.len() {
+        let (left, right) = players.split_at_mut(i + 1);
+        let a = &mut left[i];
+        for b in right.iter_mut() {
+            if !solid(a) || !solid(b) {
+                continue;
+            }
+
+            // players don't detect each other's layer, compare the shapes directly
+            let overlap = a
+                .body
+                .shape()
+                .contact(b.body.shape())
+                .map_or(0.0, |contact| contact.depth);
+            if overlap <= 0.0 {
+                continue;
+            }
+
+            // a on the left moves left, b right
+            let push = overlap * PLAYER_PUSH;
+            let direction = if a.center().x <= b.center().x {
+                -1.0
+            } else {
+                1.0
+            };
+            a.body.collider.position.x += push * direction;
+            b.body.collider.position.x -= push * direction;
+        }
+    }
+}
+
+/// Health, stamina & stocks of each player along the bottom of the screen,
+/// the match clock at the top
+fn draw_hud(players: &[Player], versus: &Match, raylib: &mut RaylibDrawHandle) {
+    const WIDTH: i32 = 220;
+    const MARGIN: i32 = 20;
+
+    let y = raylib.get_screen_height() - 70;
+    for (id, player) in players.iter().enumerate() {
+        let x = MARGIN + id as i32 * (WIDTH + MARGIN);
+        let health = player.health / player.max_health;
+        let stamina = player.stamina / player.max_stamina;
+        let stamina_color = if player.exhausted {
+            Color::GRAY
+        } else {
+            Color::SKYBLUE
+        };
+
+        raylib.draw_text(&format!("P{}", id + 1), x, y, 20, player.color);
+        if player.state == PlayerState::Dead {
+            raylib.draw_text("KO", x + 40, y, 20, Color::RED);
+        }
+
+        raylib.draw_rectangle(x, y + 24, WIDTH, 12, Color::DARKGRAY);
+        raylib.draw_rectangle(x, y + 24, (WIDTH as f32 * health) as i32, 12, Color::RED);
+        raylib.draw_rectangle(x, y + 40, WIDTH, 6, Color::DARKGRAY);
+        raylib.draw_rectangle(x, y + 40, (WIDTH as f32 * stamina) as i32, 6, stamina_color);
+
+        // lives left
+        let stocks = versus.records[id].stocks.unwrap_or(0);
+        for stock in 0..stocks as i32 {
+            raylib.draw_circle(x + WIDTH - 8 - stock * 20, y + 10, 7.0, player.color);
+        }
+    }
+
+    if let Some(time_left) = versus.time_left() {
+        let text = versus::clock(time_left);
+        let x = (raylib.get_screen_width() - measure_text(&text, 40)) / 2;
+        raylib.draw_text(&text, x, 20, 40, Color::WHITE);
+    }
+}
+
+/// Countdown before the fight & the call once it's over
+fn draw_match_banner(versus: &Match, raylib: &mut RaylibDrawHandle) {
+    const GO_TIME: f32 = 0.75;
+
+    let text = match versus.phase {
+        MatchPhase::Countdown(left) => left.ceil().to_string(),
+        MatchPhase::Fighting if versus.elapsed < GO_TIME => "GO!".to_string(),
+        MatchPhase::Finished(_) => match versus.rules.mode {
+            MatchMode::Stock(_) => "GAME!".to_string(),
+            MatchMode::Timed(_) => "TIME!".to_string(),
+        },
+        MatchPhase::Fighting => return,
+    };
+
+    let x = (raylib.get_screen_width() - measure_text(&text, 100)) / 2;
+    let y = raylib.get_screen_height() / 2 - 50;
+    raylib.draw_text(&text, x, y, 100, Color::GOLD);
+}
@@ -0,0 +1,214 @@
+use crate::player::{Player, PlayerState};
+use std::cmp::Ordering;
+
+mod record;
+mod rules;
+
+pub use record::PlayerRecord;
+pub use rules::{MatchMode, MatchRules};
+
+/// Seconds the final blow is shown before the results
+const FINISH_DELAY: f32 = 2.0;
+/// Seconds a hit still earns credit for the target's knock out
+const KO_CREDIT_TIME: f32 = 5.0;
+
+/// Where a match is at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatchPhase {
+    /// Seconds left before the fight starts
+    Countdown(f32),
+    Fighting,
+    /// Seconds left before the results
+    Finished(f32),
+}
+
+/// Who won, players still tied after every tie-break draw
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchOutcome {
+    Winner(usize),
+    Draw(Vec<usize>),
+}
+
+/// One versus match between the players, by their ids
+pub struct Match {
+    pub rules: MatchRules,
+    pub phase: MatchPhase,
+    /// Seconds fought
+    pub elapsed: f32,
+    pub records: Vec<PlayerRecord>,
+    pub outcome: Option<MatchOutcome>,
+    /// Last player to hit each player & when, credited with their knock out
+    last_hit_by: Vec<Option<(usize, f32)>>,
+    /// Players down last step, so each knock out counts once
+    was_down: Vec<bool>,
+}
+
+impl Match {
+    pub fn new(rules: MatchRules, players: usize) -> Self {
+        let stocks = match rules.mode {
+            MatchMode::Stock(stocks) => Some(stocks),
+            MatchMode::Timed(_) => None,
+        };
+
+        Self {
+            rules,
+            phase: MatchPhase::Countdown(rules.countdown),
+            elapsed: 0.0,
+            records: vec![PlayerRecord::new(stocks); players],
+            outcome: None,
+            last_hit_by: vec![None; players],
+            was_down: vec![false; players],
+        }
+    }
+
+    /// Players only act while fighting
+    pub fn is_fighting(&self) -> bool {
+        self.phase == MatchPhase::Fighting
+    }
+
+    /// Finished & done showing the final blow
+    pub fn is_over(&self) -> bool {
+        matches!(self.phase, MatchPhase::Finished(left) if left <= 0.0)
+    }
+
+    pub fn is_eliminated(&self, id: usize) -> bool {
+        self.records
+            .get(id)
+            .is_some_and(PlayerRecord::is_eliminated)
+    }
+
+    /// Seconds left in timed matches
+    pub fn time_left(&self) -> Option<f32> {
+        match self.rules.mode {
+            MatchMode::Timed(time) => Some((time - self.elapsed).max(0.0)),
+            MatchMode::Stock(_) => None,
+        }
+    }
+
+    /// Step the countdown, clock & player records, knock outs count as players go down
+    pub fn update(&mut self, players: &[Player], frame_time: f32) {
+        match self.phase {
+            MatchPhase::Countdown(left) if left > frame_time => {
+                self.phase = MatchPhase::Countdown(left - frame_time);
+            }
+            MatchPhase::Countdown(_) => self.phase = MatchPhase::Fighting,
+            MatchPhase::Fighting => self.update_fight(players, frame_time),
+            MatchPhase::Finished(left) => {
+                self.phase = MatchPhase::Finished((left - frame_time).max(0.0));
+            }
+        }
+    }
+
+    fn update_fight(&mut self, players: &[Player], frame_time: f32) {
+        self.elapsed += frame_time;
+
+        for (id, player) in players.iter().enumerate() {
+            let down = player.state == PlayerState::Dead;
+            if down && !self.was_down[id] {
+                self.record_knock_out(id);
+            }
+            self.was_down[id] = down;
+
+            if !down && !self.is_eliminated(id) {
+                self.records[id].time_alive += frame_time;
+            }
+        }
+
+        // out of time, or one player left standing
+        let standing = self.records.iter().filter(|r| !r.is_eliminated()).count();
+        let time_up = self.time_left().is_some_and(|left| left <= 0.0);
+        let last_stock = matches!(self.rules.mode, MatchMode::Stock(_)) && standing <= 1;
+        if time_up || last_stock {
+            self.outcome = Some(self.decide());
+            self.phase = MatchPhase::Finished(FINISH_DELAY);
+        }
+    }
+
+    /// Credit `attacker` with `damage` dealt to `target`, hazards aren't players & get no credit
+    pub fn record_hit(&mut self, attacker: usize, target: usize, damage: f32) {
+        if !self.is_fighting() {
+            return;
+        }
+
+        if let Some(record) = self.records.get_mut(attacker) {
+            record.damage_dealt += damage;
+            self.last_hit_by[target] = Some((attacker, self.elapsed));
+        }
+    }
+
+    /// Count a fall for `id` & a KO for whoever hit them recently,
+    /// falling with no recent hit is a self-destruct
+    fn record_knock_out(&mut self, id: usize) {
+        let elapsed = self.elapsed;
+        let record = &mut self.records[id];
+        record.falls += 1;
+        if let Some(stocks) = &mut record.stocks {
+            *stocks = stocks.saturating_sub(1);
+            if *stocks == 0 {
+                record.eliminated_at = Some(elapsed);
+            }
+        }
+
+        let credited = self.last_hit_by[id]
+            .take()
+            .filter(|(_, time)| elapsed - time <= KO_CREDIT_TIME);
+        match credited {
+            Some((attacker, _)) => self.records[attacker].kos += 1,
+            None => self.records[id].self_destructs += 1,
+        }
+    }
+
+    /// Player ids from first to last place
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = (0..self.records.len()).collect();
+        ids.sort_by(|&a, &b| self.compare(b, a));
+        ids
+    }
+
+    /// Players ahead of everyone else win, more than one is a draw
+    fn decide(&self) -> MatchOutcome {
+        let ranking = self.ranking();
+        let leaders: Vec<usize> = ranking
+            .iter()
+            .copied()
+            .take_while(|&id| self.compare(id, ranking[0]) == Ordering::Equal)
+            .collect();
+
+        match leaders[..] {
+            [winner] => MatchOutcome::Winner(winner),
+            _ => MatchOutcome::Draw(leaders),
+        }
+    }
+
+    /// Standing of player `a` against `b`, tie-breaks compared in order:
+    /// stocks left & surviving longest, or score & KOs, then damage dealt & time alive
+    fn compare(&self, a: usize, b: usize) -> Ordering {
+        let standing = |id: usize| {
+            let record = &self.records[id];
+            match self.rules.mode {
+                MatchMode::Stock(_) => [
+                    record.stocks.unwrap_or(0) as f32,
+                    record.eliminated_at.unwrap_or(f32::INFINITY),
+                    record.damage_dealt,
+                    record.time_alive,
+                ],
+                MatchMode::Timed(_) => [
+                    record.score() as f32,
+                    record.kos as f32,
+                    record.damage_dealt,
+                    record.time_alive,
+                ],
+            }
+        };
+
+        standing(a)
+            .partial_cmp(&standing(b))
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Seconds as a `m:ss` clock
+pub fn clock(seconds: f32) -> String {
+    let seconds = seconds.ceil() as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
@@ -0,0 +1,36 @@
+/// What one player did over a match
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlayerRecord {
+    pub damage_dealt: f32,
+    /// Opponents knocked out
+    pub kos: u32,
+    /// Times knocked out
+    pub falls: u32,
+    /// Falls without a recent hit to credit, counted among the falls
+    pub self_destructs: u32,
+    /// Seconds fought without being knocked out
+    pub time_alive: f32,
+    /// Lives left, in stock matches
+    pub stocks: Option<u32>,
+    /// Match time the last stock was lost
+    pub eliminated_at: Option<f32>,
+}
+
+impl PlayerRecord {
+    pub fn new(stocks: Option<u32>) -> Self {
+        Self {
+            stocks,
+            ..Default::default()
+        }
+    }
+
+    /// Out of stocks, no more respawns
+    pub fn is_eliminated(&self) -> bool {
+        self.eliminated_at.is_some()
+    }
+
+    /// KOs less falls, scoring timed matches
+    pub fn score(&self) -> i32 {
+        self.kos as i32 - self.falls as i32
+    }
+}
@@ -0,0 +1,63 @@
+/// How a match is won
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatchMode {
+    /// Last player with lives left wins
+    Stock(u32),
+    /// Most KOs, less falls, once the seconds run out
+    Timed(f32),
+}
+
+impl MatchMode {
+    pub const DEFAULT_STOCKS: u32 = 3;
+    pub const DEFAULT_TIME: f32 = 120.0;
+    /// Seconds added or removed per adjustment of a timed match
+    pub const TIME_STEP: f32 = 30.0;
+
+    /// Other mode, at its default length
+    pub fn switched(&self) -> Self {
+        match self {
+            MatchMode::Stock(_) => MatchMode::Timed(MatchMode::DEFAULT_TIME),
+            MatchMode::Timed(_) => MatchMode::Stock(MatchMode::DEFAULT_STOCKS),
+        }
+    }
+
+    /// Longer or shorter by `steps` stocks or time steps, at least one of either
+    pub fn adjusted(&self, steps: i32) -> Self {
+        match *self {
+            MatchMode::Stock(stocks) => MatchMode::Stock((stocks as i32 + steps).max(1) as u32),
+            MatchMode::Timed(time) => MatchMode::Timed(
+                (time + steps as f32 * MatchMode::TIME_STEP).max(MatchMode::TIME_STEP),
+            ),
+        }
+    }
+}
+
+/// Settings of a versus match
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatchRules {
+    pub mode: MatchMode,
+    /// Seconds counted down before the fight starts
+    pub countdown: f32,
+}
+
+impl Default for MatchRules {
+    fn default() -> Self {
+        MatchRules::new(MatchMode::Stock(MatchMode::DEFAULT_STOCKS))
+    }
+}
+
+impl MatchRules {
+    pub const DEFAULT_COUNTDOWN: f32 = 3.0;
+
+    pub const fn new(mode: MatchMode) -> Self {
+        Self {
+            mode,
+            countdown: MatchRules::DEFAULT_COUNTDOWN,
+        }
+    }
+
+    pub const fn with_countdown(mut self, countdown: f32) -> Self {
+        self.countdown = countdown;
+        self
+    }
+}